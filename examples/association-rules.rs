@@ -24,7 +24,7 @@ use fpgrowth_rs::{fp_growth, generate_association_rules};
 /// the resulting association rules, the "antecedent support" for "potato chips" shows 0.45. This
 /// leads to the incorrect confidence and lifts in that table:
 ///  - The table shows a lift of 1.81 for "potato chips" -> "beer" and to
-///     "beer" -> "potato chips", the correct is 1.63.
+///    "beer" -> "potato chips", the correct is 1.63.
 ///  - The table shows the confidence for "potato chips" -> "beer" to be 1.0, the correct is 0.9.
 ///
 fn main() {
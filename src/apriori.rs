@@ -0,0 +1,238 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashSet};
+
+/// The number of children of an interior node of the hash tree.
+const FAN_OUT: usize = 8;
+
+/// The number of candidates a leaf holds before it is split.
+const MAX_LEAF_SIZE: usize = 16;
+
+/// The support of each candidate, and the last transaction in which it was counted.
+struct SupportCounts {
+    counts: Vec<usize>,
+    last_transaction: Vec<usize>,
+}
+
+/// A hash tree of candidate item sets of the same size. Interior nodes at depth `d` hash the
+/// `d`-th item of a candidate, and leaves hold the candidates themselves.
+#[derive(Debug)]
+enum HashTree {
+    Interior(Vec<HashTree>),
+    Leaf(Vec<usize>),
+}
+
+impl HashTree {
+    fn insert(&mut self, candidates: &[Vec<usize>], candidate: usize, depth: usize) {
+        match self {
+            HashTree::Interior(children) => {
+                let item = candidates[candidate][depth];
+                children[item % FAN_OUT].insert(candidates, candidate, depth + 1);
+            }
+            HashTree::Leaf(leaf) => {
+                leaf.push(candidate);
+                // Leaves at the depth of the candidate size can't be split any further.
+                if leaf.len() > MAX_LEAF_SIZE && depth < candidates[candidate].len() {
+                    let leaf = std::mem::take(leaf);
+                    *self =
+                        HashTree::Interior((0..FAN_OUT).map(|_| HashTree::Leaf(vec![])).collect());
+                    for candidate in leaf {
+                        self.insert(candidates, candidate, depth);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Counts the candidates contained in the transaction, which must be sorted. As a leaf can be
+    /// reached through different items of the transaction, the last transaction each candidate was
+    /// counted in ensures it is only counted once per transaction.
+    fn count(
+        &self,
+        candidates: &[Vec<usize>],
+        transaction: &[usize],
+        transaction_index: usize,
+        start: usize,
+        depth: usize,
+        support_counts: &mut SupportCounts,
+    ) {
+        match self {
+            HashTree::Interior(children) => {
+                let size = candidates[0].len();
+                // Leave enough items in the transaction to complete the candidate.
+                let end = (transaction.len() + 1).saturating_sub(size - depth);
+                for index in start..end {
+                    children[transaction[index] % FAN_OUT].count(
+                        candidates,
+                        transaction,
+                        transaction_index,
+                        index + 1,
+                        depth + 1,
+                        support_counts,
+                    );
+                }
+            }
+            HashTree::Leaf(leaf) => {
+                for candidate in leaf {
+                    if support_counts.last_transaction[*candidate] != transaction_index
+                        && is_subset(&candidates[*candidate], transaction)
+                    {
+                        support_counts.last_transaction[*candidate] = transaction_index;
+                        support_counts.counts[*candidate] += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether the sorted `subset` is contained in the sorted `set`.
+fn is_subset(subset: &[usize], set: &[usize]) -> bool {
+    let mut set = set.iter();
+    subset.iter().all(|item| set.any(|i| i == item))
+}
+
+/// Joins frequent item sets of the same size sharing all items but the last, keeping the
+/// candidates which subsets are all frequent.
+fn generate_candidates(frequent_item_sets: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let size = frequent_item_sets[0].len();
+    let frequent = frequent_item_sets.iter().collect::<HashSet<_>>();
+    let mut candidates = vec![];
+    for (i, a) in frequent_item_sets.iter().enumerate() {
+        for b in &frequent_item_sets[i + 1..] {
+            if a[..size - 1] != b[..size - 1] {
+                break;
+            }
+            let mut candidate = a.clone();
+            candidate.push(b[size - 1]);
+            let subsets_are_frequent = (0..size - 1).all(|skip| {
+                let mut subset = candidate.clone();
+                subset.remove(skip);
+                frequent.contains(&subset)
+            });
+            if subsets_are_frequent {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates
+}
+
+/// Collects frequent item sets from the provided transactions, using the Apriori algorithm.
+///
+/// Frequent item sets are found level by level: candidates of size `k + 1` are generated by joining
+/// the frequent item sets of size `k`, and the ones with an infrequent subset are pruned. The
+/// remaining candidates are stored in a hash tree, which is used to count their support in a
+/// single scan of the transactions. Item sets are reported with their items in lexicographic
+/// order.
+///
+/// Apriori scans the transactions once per level, so it is usually slower than [`fp_growth`],
+/// but it is simpler and finds the same item sets.
+///
+/// [`fp_growth`]: crate::fp_growth
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+/// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+///   support is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::apriori;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// apriori(transactions.as_slice(), 3, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+pub fn apriori<F>(transactions: &[Vec<&str>], min_support: usize, mut collect: F)
+where
+    F: FnMut(&[&str], usize),
+{
+    // Item sets that never occur are not frequent, whatever the minimum support.
+    let min_support = min_support.max(1);
+    let mut frequencies = BTreeMap::new();
+    for transaction in transactions {
+        for item in transaction.iter().collect::<HashSet<_>>() {
+            *frequencies.entry(*item).or_insert(0) += 1;
+        }
+    }
+
+    // Frequent items are replaced by their rank in lexicographic order, and infrequent items are
+    // dropped from the transactions.
+    let items = frequencies
+        .into_iter()
+        .filter(|(_, frequency)| *frequency >= min_support)
+        .map(|(item, frequency)| {
+            collect(&[item], frequency);
+            item
+        })
+        .collect::<Vec<_>>();
+    let transactions = transactions
+        .iter()
+        .map(|transaction| {
+            let mut transaction = transaction
+                .iter()
+                .filter_map(|item| items.binary_search(item).ok())
+                .collect::<Vec<_>>();
+            transaction.sort();
+            transaction.dedup();
+            transaction
+        })
+        .collect::<Vec<_>>();
+
+    let mut frequent_item_sets = (0..items.len()).map(|item| vec![item]).collect::<Vec<_>>();
+    while frequent_item_sets.len() > 1 {
+        let candidates = generate_candidates(&frequent_item_sets);
+        if candidates.is_empty() {
+            break;
+        }
+        let size = candidates[0].len();
+        let mut hash_tree = HashTree::Leaf(vec![]);
+        for candidate in 0..candidates.len() {
+            hash_tree.insert(&candidates, candidate, 0);
+        }
+
+        let mut support_counts = SupportCounts {
+            counts: vec![0; candidates.len()],
+            last_transaction: vec![usize::MAX; candidates.len()],
+        };
+        for (index, transaction) in transactions.iter().enumerate() {
+            if transaction.len() >= size {
+                hash_tree.count(&candidates, transaction, index, 0, 0, &mut support_counts);
+            }
+        }
+
+        frequent_item_sets = vec![];
+        for (candidate, count) in candidates.into_iter().zip(support_counts.counts) {
+            if count >= min_support {
+                let item_set = candidate.iter().map(|i| items[*i]).collect::<Vec<_>>();
+                collect(item_set.as_slice(), count);
+                frequent_item_sets.push(candidate);
+            }
+        }
+    }
+}
@@ -12,8 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// An association rule between an antecedent and a consequent item set.
+///
+/// Either side of the rule may be negated, meaning the absence of its item set. For instance,
+/// `{A} -> ¬{B}` means that transactions containing `A` tend not to contain `B`. Supports always
+/// refer to the side as it appears in the rule, so the `antecedent_support` of `¬{A} -> {B}` is the
+/// fraction of transactions without `A`.
 #[derive(Debug, PartialEq)]
 pub struct AssociationRule {
     pub antecedent_set: Vec<String>,
@@ -23,6 +32,8 @@ pub struct AssociationRule {
     pub combined_support: f32,
     pub lift: f32,
     pub confidence: f32,
+    pub antecedent_negated: bool,
+    pub consequent_negated: bool,
 }
 
 impl AssociationRule {
@@ -43,24 +54,317 @@ impl AssociationRule {
             combined_support,
             lift,
             confidence,
+            antecedent_negated: false,
+            consequent_negated: false,
         }
     }
+
+    /// Whether either side of the rule is negated.
+    pub fn is_negative(&self) -> bool {
+        self.antecedent_negated || self.consequent_negated
+    }
 }
 
 /// Generates association rules from frequent item sets.
+///
+/// Rules are generated for every split of each item set into a non-empty antecedent and
+/// consequent, so consequents may have several items, as in `{A} -> {B, C}`.
 pub fn generate_association_rules<F>(
     frequent_item_sets: &HashMap<Vec<String>, usize>,
     num_transactions: usize,
     on_association_rule: &mut F,
 ) where
     F: FnMut(AssociationRule),
+{
+    generate_confident_association_rules(
+        frequent_item_sets,
+        num_transactions,
+        0.0,
+        on_association_rule,
+    );
+}
+
+/// Generates association rules from frequent item sets, which confidence is at least
+/// `min_confidence`.
+///
+/// Works as [`generate_association_rules`], but consequents stop growing as soon as rules fall
+/// below the minimum confidence, so rules that could not match it are never computed.
+///
+/// # Arguments
+/// * `frequent_item_sets` - frequent item sets, with their items sorted.
+/// * `num_transactions` - the number of transactions the item sets were found in.
+/// * `min_confidence` - the minimum confidence of the rules.
+/// * `on_association_rule` - a closure that will be invoked for each rule.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+///
+/// use fpgrowth_rs::{fp_growth, generate_confident_association_rules};
+///
+/// let transactions = vec![
+///     vec!["A", "B", "C"],
+///     vec!["A", "B", "C"],
+///     vec!["A", "B"],
+///     vec!["A"],
+/// ];
+///
+/// let mut frequent_item_sets = HashMap::new();
+/// fp_growth(transactions.as_slice(), 2, |item_set, occurences| {
+///     let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+///     item_set.sort();
+///     frequent_item_sets.insert(item_set, occurences);
+/// });
+///
+/// let mut rules = vec![];
+/// generate_confident_association_rules(&frequent_item_sets, transactions.len(), 0.6, &mut |rule| {
+///     rules.push((rule.antecedent_set, rule.consequent_set))
+/// });
+/// assert!(rules.contains(&(vec!["B".to_string()], vec!["A".to_string(), "C".to_string()])));
+/// assert!(!rules.contains(&(vec!["A".to_string()], vec!["B".to_string(), "C".to_string()])));
+/// ```
+pub fn generate_confident_association_rules<F>(
+    frequent_item_sets: &HashMap<Vec<String>, usize>,
+    num_transactions: usize,
+    min_confidence: f32,
+    on_association_rule: &mut F,
+) where
+    F: FnMut(AssociationRule),
 {
+    for set in frequent_item_sets.keys() {
+        generate_item_set_rules(
+            set,
+            frequent_item_sets,
+            num_transactions,
+            min_confidence,
+            on_association_rule,
+        );
+    }
+}
+
+/// The number of item sets which rules are generated in parallel before being reported.
+#[cfg(feature = "parallel")]
+const CHUNK_SIZE: usize = 4096;
+
+/// Generates association rules from frequent item sets, using a parallel implementation.
+///
+/// Gives the same rules as [`generate_association_rules`]. Item sets are split in chunks, and the
+/// rules of the item sets in a chunk are generated in parallel, before being reported in order on
+/// the calling thread.
+///
+/// # Arguments
+/// * `frequent_item_sets` - frequent item sets, with their items sorted.
+/// * `num_transactions` - the number of transactions the item sets were found in.
+/// * `deterministic` - whether rules are reported in a deterministic order, sorted by the item
+///   set they come from. Otherwise, item sets are taken in the order of `frequent_item_sets`.
+/// * `on_association_rule` - a closure that will be invoked for each rule.
+///
+/// # Example
+/// ```
+/// use std::collections::{HashMap, HashSet};
+///
+/// use fpgrowth_rs::{fp_growth, par_generate_association_rules};
+///
+/// let transactions = vec![
+///     vec!["beer", "potato chips"],
+///     vec!["beer", "potato chips", "wine"],
+///     vec!["wine", "cheese"],
+/// ];
+///
+/// let mut frequent_item_sets = HashMap::new();
+/// fp_growth(transactions.as_slice(), 2, |item_set, occurences| {
+///     let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+///     item_set.sort();
+///     frequent_item_sets.insert(item_set, occurences);
+/// });
+///
+/// par_generate_association_rules(&frequent_item_sets, transactions.len(), true, &mut |rule| {
+///     println!("{:?} -> {:?}", rule.antecedent_set, rule.consequent_set)
+/// });
+/// ```
+#[cfg(feature = "parallel")]
+pub fn par_generate_association_rules<F>(
+    frequent_item_sets: &HashMap<Vec<String>, usize>,
+    num_transactions: usize,
+    deterministic: bool,
+    on_association_rule: &mut F,
+) where
+    F: FnMut(AssociationRule),
+{
+    let mut sets = frequent_item_sets.keys().collect::<Vec<_>>();
+    if deterministic {
+        sets.par_sort_unstable();
+    }
+    for chunk in sets.chunks(CHUNK_SIZE) {
+        let rules = chunk
+            .par_iter()
+            .map(|set| {
+                let mut rules = vec![];
+                generate_item_set_rules(
+                    set,
+                    frequent_item_sets,
+                    num_transactions,
+                    0.0,
+                    &mut |rule| rules.push(rule),
+                );
+                rules
+            })
+            .collect::<Vec<_>>();
+        for rule in rules.into_iter().flatten() {
+            on_association_rule(rule);
+        }
+    }
+}
+
+/// Generates association rules from rare item sets, which are the ones with a support below
+/// `max_support`.
+///
+/// Subsets of a rare item set may be frequent, so `item_sets` must contain every item set with a
+/// support of at least the `min_support` used to find the rare item sets, as collected by
+/// [`fp_growth`](crate::fp_growth). Rules are only generated for the rare item sets among them.
+pub fn generate_rare_association_rules<F>(
+    item_sets: &HashMap<Vec<String>, usize>,
+    num_transactions: usize,
+    max_support: usize,
+    on_association_rule: &mut F,
+) where
+    F: FnMut(AssociationRule),
+{
+    for (set, occurences) in item_sets {
+        if *occurences < max_support {
+            generate_item_set_rules(set, item_sets, num_transactions, 0.0, on_association_rule);
+        }
+    }
+}
+
+/// The rule of an item set which consequent holds the items at the positions in `consequent`, and
+/// which antecedent holds the other items.
+pub(crate) fn item_set_rule(
+    set: &[String],
+    consequent: &[usize],
+    item_sets: &HashMap<Vec<String>, usize>,
+    num_transactions: usize,
+) -> AssociationRule {
+    let support = |items: Vec<String>| {
+        let support = *item_sets.get(&items).unwrap() as f32 / num_transactions as f32;
+        (items, support)
+    };
+    let (_, combined_support) = support(set.to_vec());
+    let (antecedent_set, antecedent_support) = support(
+        (0..set.len())
+            .filter(|i| !consequent.contains(i))
+            .map(|i| set[i].clone())
+            .collect(),
+    );
+    let (consequent_set, consequent_support) =
+        support(consequent.iter().map(|i| set[*i].clone()).collect());
+
+    let lift = combined_support / (antecedent_support * consequent_support);
+    let confidence = combined_support / antecedent_support;
+    AssociationRule::new(
+        antecedent_set,
+        consequent_set,
+        antecedent_support,
+        consequent_support,
+        combined_support,
+        lift,
+        confidence,
+    )
+}
+
+/// Generates the rules of an item set, for every split of its items into a non-empty antecedent
+/// and consequent, which confidence is at least `min_confidence`.
+///
+/// Consequents grow one item at a time, as in Apriori. Moving items from the antecedent to the
+/// consequent can only lower the confidence of a rule, so a consequent is only grown when every
+/// consequent one item smaller gave a confident rule.
+fn generate_item_set_rules<F>(
+    set: &[String],
+    item_sets: &HashMap<Vec<String>, usize>,
+    num_transactions: usize,
+    min_confidence: f32,
+    on_association_rule: &mut F,
+) where
+    F: FnMut(AssociationRule),
+{
+    // Consequents are the positions of their items in the item set, in increasing order.
+    let mut consequents = (0..set.len()).map(|i| vec![i]).collect::<Vec<_>>();
+    while !consequents.is_empty() && consequents[0].len() < set.len() {
+        let mut confident = vec![];
+        for consequent in consequents {
+            let rule = item_set_rule(set, &consequent, item_sets, num_transactions);
+            if rule.confidence < min_confidence {
+                continue;
+            }
+            on_association_rule(rule);
+            confident.push(consequent);
+        }
+        consequents = grow_consequents(&confident);
+    }
+}
+
+/// Joins consequents that only differ by their last item into consequents one item larger, and
+/// keeps the ones which smaller consequents are all in `consequents`.
+fn grow_consequents(consequents: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let known = consequents.iter().collect::<HashSet<_>>();
+    let mut grown = vec![];
+    for (index, a) in consequents.iter().enumerate() {
+        let prefix = &a[..a.len() - 1];
+        for b in &consequents[index + 1..] {
+            if !b.starts_with(prefix) {
+                break;
+            }
+            let mut consequent = a.clone();
+            consequent.push(b[b.len() - 1]);
+            let all_known = (0..consequent.len() - 2).all(|skip| {
+                let mut subset = consequent.clone();
+                subset.remove(skip);
+                known.contains(&subset)
+            });
+            if all_known {
+                grown.push(consequent);
+            }
+        }
+    }
+    grown
+}
+
+/// Generates negative association rules from frequent item sets, in the forms `X -> ¬Y`,
+/// `¬X -> Y` and `¬X -> ¬Y`.
+///
+/// Rules are derived from the splits of each item set into an antecedent and a single item
+/// consequent, with supports calculated from the supports of the positive item
+/// sets. For example, the support of `X -> ¬Y` is `supp(X) - supp(X ∪ Y)`, and the support of
+/// `¬X -> ¬Y` is `1 - supp(X) - supp(Y) + supp(X ∪ Y)`.
+///
+/// Only interesting rules are generated. The interest of a pair of item sets is
+/// `|supp(X ∪ Y) - supp(X) * supp(Y)|`, and measures how far they are from being independent. When
+/// `X` and `Y` are negatively correlated, `X -> ¬Y` and `¬X -> Y` are generated, and when they are
+/// positively correlated, `¬X -> ¬Y` is generated.
+///
+/// # Arguments
+/// * `frequent_item_sets` - frequent item sets, with their items sorted.
+/// * `num_transactions` - the number of transactions the item sets were found in.
+/// * `min_confidence` - the minimum confidence of the generated rules.
+/// * `min_interest` - the minimum interest of the pair of item sets in a rule.
+/// * `on_association_rule` - a closure that will be invoked for each negative rule.
+pub fn generate_negative_association_rules<F>(
+    frequent_item_sets: &HashMap<Vec<String>, usize>,
+    num_transactions: usize,
+    min_confidence: f32,
+    min_interest: f32,
+    on_association_rule: &mut F,
+) where
+    F: FnMut(AssociationRule),
+{
+    let support =
+        |set: &Vec<String>| *frequent_item_sets.get(set).unwrap() as f32 / num_transactions as f32;
+
     for set in frequent_item_sets.keys() {
         if set.len() == 1 {
             continue;
         }
-        let combined_support =
-            *frequent_item_sets.get(set).unwrap() as f32 / num_transactions as f32;
+        let combined_support = support(set);
         for item in set {
             let antecedent_set = set
                 .iter()
@@ -68,22 +372,61 @@ pub fn generate_association_rules<F>(
                 .cloned()
                 .collect::<Vec<_>>();
             let consequent_set = vec![item.clone()];
-            let antecedent_support =
-                *frequent_item_sets.get(&antecedent_set).unwrap() as f32 / num_transactions as f32;
-            let consequent_support =
-                *frequent_item_sets.get(&consequent_set).unwrap() as f32 / num_transactions as f32;
-
-            let lift = combined_support / (antecedent_support * consequent_support);
-            let confidence = combined_support / antecedent_support;
-            on_association_rule(AssociationRule::new(
-                antecedent_set,
-                consequent_set,
-                antecedent_support,
-                consequent_support,
-                combined_support,
-                lift,
-                confidence,
-            ));
+            let antecedent_support = support(&antecedent_set);
+            let consequent_support = support(&consequent_set);
+
+            let expected_support = antecedent_support * consequent_support;
+            if (combined_support - expected_support).abs() < min_interest {
+                continue;
+            }
+
+            // Negatively correlated item sets generate X -> ¬Y and ¬X -> Y, and positively
+            // correlated ones generate ¬X -> ¬Y.
+            let negations = if combined_support < expected_support {
+                [(false, true), (true, false)].as_slice()
+            } else {
+                [(true, true)].as_slice()
+            };
+            for (antecedent_negated, consequent_negated) in negations {
+                let (rule_antecedent_support, rule_consequent_support, rule_support) =
+                    match (antecedent_negated, consequent_negated) {
+                        (false, true) => (
+                            antecedent_support,
+                            1.0 - consequent_support,
+                            antecedent_support - combined_support,
+                        ),
+                        (true, false) => (
+                            1.0 - antecedent_support,
+                            consequent_support,
+                            consequent_support - combined_support,
+                        ),
+                        _ => (
+                            1.0 - antecedent_support,
+                            1.0 - consequent_support,
+                            1.0 - antecedent_support - consequent_support + combined_support,
+                        ),
+                    };
+                if rule_antecedent_support <= 0.0 || rule_consequent_support <= 0.0 {
+                    continue;
+                }
+
+                let confidence = rule_support / rule_antecedent_support;
+                if confidence < min_confidence {
+                    continue;
+                }
+                let mut association_rule = AssociationRule::new(
+                    antecedent_set.clone(),
+                    consequent_set.clone(),
+                    rule_antecedent_support,
+                    rule_consequent_support,
+                    rule_support,
+                    confidence / rule_consequent_support,
+                    confidence,
+                );
+                association_rule.antecedent_negated = *antecedent_negated;
+                association_rule.consequent_negated = *consequent_negated;
+                on_association_rule(association_rule);
+            }
         }
     }
 }
@@ -0,0 +1,241 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::association_rules::item_set_rule;
+use crate::{fp_growth, AssociationRule};
+
+/// Generates class association rules from frequent item sets, which are the association rules
+/// which consequent is a single class label, and which antecedent contains no class labels.
+///
+/// # Arguments
+/// * `frequent_item_sets` - frequent item sets, with their items sorted.
+/// * `num_transactions` - the number of transactions the item sets were found in.
+/// * `class_labels` - the items that are class labels.
+/// * `on_association_rule` - a closure that will be invoked for each class association rule.
+pub fn generate_class_association_rules<F>(
+    frequent_item_sets: &HashMap<Vec<String>, usize>,
+    num_transactions: usize,
+    class_labels: &[&str],
+    on_association_rule: &mut F,
+) where
+    F: FnMut(AssociationRule),
+{
+    // Each item set with a single class label and other items gives one class association rule.
+    for set in frequent_item_sets.keys() {
+        let mut labels = set
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| class_labels.contains(&item.as_str()))
+            .map(|(position, _)| position);
+        if let (Some(label), None) = (labels.next(), labels.next()) {
+            if set.len() > 1 {
+                on_association_rule(item_set_rule(
+                    set,
+                    &[label],
+                    frequent_item_sets,
+                    num_transactions,
+                ));
+            }
+        }
+    }
+}
+
+/// Orders rules by decreasing confidence, then by decreasing support, and then by increasing
+/// antecedent size, which is the precedence of rules in CBA.
+fn compare_rules(a: &AssociationRule, b: &AssociationRule) -> Ordering {
+    b.confidence
+        .partial_cmp(&a.confidence)
+        .unwrap_or(Ordering::Equal)
+        .then(
+            b.combined_support
+                .partial_cmp(&a.combined_support)
+                .unwrap_or(Ordering::Equal),
+        )
+        .then(a.antecedent_set.len().cmp(&b.antecedent_set.len()))
+        .then_with(|| a.antecedent_set.cmp(&b.antecedent_set))
+        .then_with(|| a.consequent_set.cmp(&b.consequent_set))
+}
+
+/// The most common class among the labels, preferring the first in lexicographic order on ties.
+fn majority_class<'a>(labels: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let mut counts = BTreeMap::new();
+    for label in labels {
+        *counts.entry(label).or_insert(0) += 1;
+    }
+    counts
+        .into_iter()
+        .fold(
+            None,
+            |best: Option<(&str, usize)>, (label, count)| match best {
+                Some((_, best_count)) if best_count >= count => best,
+                _ => Some((label, count)),
+            },
+        )
+        .map(|(label, _)| label)
+}
+
+/// A classifier built from class association rules, as described in CBA (Classification Based on
+/// Associations).
+///
+/// Each training transaction contains the items describing it and one of the class labels. The
+/// class association rules mined from them are ranked, and pruned by database coverage: rules are
+/// taken in order, and a rule is kept when it correctly classifies at least one of the transactions
+/// not covered by the rules kept before it. The list is then cut where the number of training
+/// errors, including the ones made by the default class, is the lowest.
+///
+/// Transactions are classified by the first rule which antecedent they contain, or by the default
+/// class when none does.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::CbaClassifier;
+///
+/// let transactions = vec![
+///     vec!["monthly", "support calls", "churn"],
+///     vec!["monthly", "support calls", "churn"],
+///     vec!["monthly", "stay"],
+///     vec!["yearly", "stay"],
+///     vec!["yearly", "support calls", "stay"],
+/// ];
+///
+/// let classifier = CbaClassifier::train(transactions.as_slice(), &["churn", "stay"], 2, 0.6);
+/// assert_eq!(classifier.predict(&["monthly", "support calls"]), Some("churn"));
+/// ```
+#[derive(Debug)]
+pub struct CbaClassifier {
+    rules: Vec<AssociationRule>,
+    default_class: Option<String>,
+}
+
+impl CbaClassifier {
+    /// Trains a classifier on labelled transactions.
+    ///
+    /// # Arguments
+    /// * `transactions` - a list of transactions, each containing one of the class labels.
+    ///   Transactions without a class label are ignored when pruning rules.
+    /// * `class_labels` - the items that are class labels.
+    /// * `min_support` - the minimum support of the rules.
+    /// * `min_confidence` - the minimum confidence of the rules.
+    pub fn train(
+        transactions: &[Vec<&str>],
+        class_labels: &[&str],
+        min_support: usize,
+        min_confidence: f32,
+    ) -> Self {
+        let mut frequent_item_sets = HashMap::new();
+        fp_growth(transactions, min_support, |item_set, occurences| {
+            let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            item_set.sort();
+            frequent_item_sets.insert(item_set, occurences);
+        });
+
+        let mut rules = vec![];
+        generate_class_association_rules(
+            &frequent_item_sets,
+            transactions.len(),
+            class_labels,
+            &mut |rule| {
+                if rule.confidence >= min_confidence {
+                    rules.push(rule);
+                }
+            },
+        );
+        rules.sort_by(compare_rules);
+
+        let mut uncovered = transactions
+            .iter()
+            .filter_map(|t| {
+                let label = t.iter().find(|i| class_labels.contains(i))?;
+                Some((t.iter().copied().collect::<HashSet<_>>(), *label))
+            })
+            .collect::<Vec<_>>();
+
+        // Keeps the rules that correctly classify at least one uncovered transaction, tracking the
+        // total number of errors if the list was cut after each of them.
+        let mut selected = vec![];
+        let mut rule_errors = 0;
+        let mut default_class = majority_class(uncovered.iter().map(|(_, label)| *label));
+        let mut best_errors = uncovered
+            .iter()
+            .filter(|(_, label)| Some(*label) != default_class)
+            .count();
+        let mut best = (0, default_class.map(str::to_string));
+        for rule in rules {
+            let (covered, remaining): (Vec<_>, Vec<_>) =
+                uncovered.into_iter().partition(|(items, _)| {
+                    rule.antecedent_set
+                        .iter()
+                        .all(|i| items.contains(i.as_str()))
+                });
+            uncovered = remaining;
+            let consequent = rule.consequent_set[0].as_str();
+            let correct = covered
+                .iter()
+                .filter(|(_, label)| *label == consequent)
+                .count();
+            if correct == 0 {
+                uncovered.extend(covered);
+                continue;
+            }
+
+            rule_errors += covered.len() - correct;
+            selected.push(rule);
+            if !uncovered.is_empty() {
+                default_class = majority_class(uncovered.iter().map(|(_, label)| *label));
+            }
+            let errors = rule_errors
+                + uncovered
+                    .iter()
+                    .filter(|(_, label)| Some(*label) != default_class)
+                    .count();
+            if errors < best_errors {
+                best_errors = errors;
+                best = (selected.len(), default_class.map(str::to_string));
+            }
+        }
+
+        selected.truncate(best.0);
+        Self {
+            rules: selected,
+            default_class: best.1,
+        }
+    }
+
+    /// The rules of the classifier, in the order they are applied.
+    pub fn rules(&self) -> &[AssociationRule] {
+        &self.rules
+    }
+
+    /// The class predicted for transactions not covered by any rule.
+    pub fn default_class(&self) -> Option<&str> {
+        self.default_class.as_deref()
+    }
+
+    /// Predicts the class of a transaction, returning `None` only when the classifier was trained
+    /// without labelled transactions.
+    pub fn predict(&self, transaction: &[&str]) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|rule| {
+                rule.antecedent_set
+                    .iter()
+                    .all(|i| transaction.contains(&i.as_str()))
+            })
+            .map(|rule| rule.consequent_set[0].as_str())
+            .or(self.default_class())
+    }
+}
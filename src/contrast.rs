@@ -0,0 +1,192 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+
+use crate::fpgrowth::{fp_growth_tree, FpTree};
+
+/// An item set which support differs between two datasets, `a` and `b`.
+#[derive(Debug, PartialEq)]
+pub struct ContrastPattern {
+    pub item_set: Vec<String>,
+    pub occurences_a: usize,
+    pub occurences_b: usize,
+    pub support_a: f64,
+    pub support_b: f64,
+    /// The ratio between the support in `a` and the support in `b`. Infinite when the item set is
+    /// only found in `a`, and 0 when it is only found in `b`.
+    pub growth_rate: f64,
+    /// The support in `a` minus the support in `b`.
+    pub support_difference: f64,
+    /// The chi-squared statistic for the item set being independent of the dataset.
+    pub chi_squared: f64,
+    /// The p-value of the chi-squared test, with one degree of freedom.
+    pub p_value: f64,
+}
+
+impl ContrastPattern {
+    /// Whether the item set is a jumping emerging pattern, found in only one of the datasets.
+    pub fn is_jumping(&self) -> bool {
+        (self.occurences_a == 0) != (self.occurences_b == 0)
+    }
+}
+
+/// The complementary error function, approximated with a fractional error below 1.2e-7.
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let polynomial = [
+        -1.26551223,
+        1.00002368,
+        0.37409196,
+        0.09678418,
+        -0.18628806,
+        0.27886807,
+        -1.13520398,
+        1.48851587,
+        -0.82215223,
+        0.17087277,
+    ]
+    .iter()
+    .rev()
+    .fold(0.0, |acc, coefficient| coefficient + t * acc);
+    let result = t * (-z * z + polynomial).exp();
+    if x >= 0.0 {
+        result
+    } else {
+        2.0 - result
+    }
+}
+
+/// Pearson's chi-squared test on the 2x2 table of datasets by presence of the item set, returning
+/// the statistic and its p-value.
+fn chi_squared_test(
+    occurences_a: usize,
+    size_a: usize,
+    occurences_b: usize,
+    size_b: usize,
+) -> (f64, f64) {
+    let observed = [
+        occurences_a as f64,
+        (size_a - occurences_a) as f64,
+        occurences_b as f64,
+        (size_b - occurences_b) as f64,
+    ];
+    let total = (size_a + size_b) as f64;
+    let present = (occurences_a + occurences_b) as f64;
+    let rows = [size_a as f64, size_b as f64];
+    let columns = [present, total - present];
+    if rows.contains(&0.0) || columns.contains(&0.0) {
+        return (0.0, 1.0);
+    }
+
+    let mut chi_squared = 0.0;
+    for (row, row_total) in rows.iter().enumerate() {
+        for (column, column_total) in columns.iter().enumerate() {
+            let expected = row_total * column_total / total;
+            chi_squared += (observed[row * 2 + column] - expected).powi(2) / expected;
+        }
+    }
+    (chi_squared, erfc((chi_squared / 2.0).sqrt()))
+}
+
+fn frequent_item_sets<'a>(
+    fp_tree: &mut FpTree<'a>,
+    min_support: usize,
+    into: &mut BTreeSet<Vec<&'a str>>,
+) {
+    // The tree keeps every item so it can count any item set, and is only mined with the minimum
+    // support.
+    fp_tree.min_support = min_support;
+    fp_growth_tree(
+        fp_tree,
+        &mut |item_set, _| {
+            let mut item_set = item_set.to_vec();
+            item_set.sort();
+            into.insert(item_set);
+        },
+        vec![],
+    );
+}
+
+/// Mines contrast patterns between two datasets, which are the item sets which support changes
+/// significantly from one dataset to the other.
+///
+/// Both datasets are mined for item sets which relative support is at least `min_support`, and
+/// each item set frequent in either of them is then counted in the other. Item sets which growth
+/// rate, in either direction, is at least `min_growth_rate` are reported, in lexicographic order.
+/// Jumping emerging patterns, which are only present in one of the datasets, have an infinite
+/// growth rate and are always reported.
+///
+/// # Arguments
+/// * `dataset_a` - the transactions of the first dataset.
+/// * `dataset_b` - the transactions of the second dataset.
+/// * `min_support` - the minimum support, relative to the size of a dataset, for an item set to
+///   be considered.
+/// * `min_growth_rate` - the minimum ratio between the supports of an item set in both datasets.
+/// * `collect` - a closure that will be invoked for each contrast pattern found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::contrast_patterns;
+///
+/// let north = vec![vec!["beer", "chips"], vec!["beer", "chips"], vec!["wine"]];
+/// let south = vec![vec!["wine", "cheese"], vec!["beer"], vec!["wine", "cheese"]];
+///
+/// contrast_patterns(north.as_slice(), south.as_slice(), 0.5, 2.0, |pattern| {
+///     println!("{:?}: {}", pattern.item_set, pattern.growth_rate)
+/// });
+/// ```
+pub fn contrast_patterns<F>(
+    dataset_a: &[Vec<&str>],
+    dataset_b: &[Vec<&str>],
+    min_support: f64,
+    min_growth_rate: f64,
+    mut collect: F,
+) where
+    F: FnMut(ContrastPattern),
+{
+    let min_occurences =
+        |dataset: &[Vec<&str>]| ((min_support * dataset.len() as f64).ceil() as usize).max(1);
+    let mut tree_a = FpTree::new(dataset_a, 1);
+    let mut tree_b = FpTree::new(dataset_b, 1);
+    let mut candidates = BTreeSet::new();
+    frequent_item_sets(&mut tree_a, min_occurences(dataset_a), &mut candidates);
+    frequent_item_sets(&mut tree_b, min_occurences(dataset_b), &mut candidates);
+
+    for item_set in candidates {
+        let occurences_a = tree_a.support(&item_set);
+        let occurences_b = tree_b.support(&item_set);
+        let support_a = occurences_a as f64 / dataset_a.len().max(1) as f64;
+        let support_b = occurences_b as f64 / dataset_b.len().max(1) as f64;
+        let growth_rate = support_a / support_b;
+        if growth_rate.max(1.0 / growth_rate) < min_growth_rate {
+            continue;
+        }
+
+        let (chi_squared, p_value) =
+            chi_squared_test(occurences_a, dataset_a.len(), occurences_b, dataset_b.len());
+        collect(ContrastPattern {
+            item_set: item_set.iter().map(|i| i.to_string()).collect(),
+            occurences_a,
+            occurences_b,
+            support_a,
+            support_b,
+            growth_rate,
+            support_difference: support_a - support_b,
+            chi_squared,
+            p_value,
+        });
+    }
+}
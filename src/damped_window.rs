@@ -0,0 +1,126 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::incremental::WeightedCanTree;
+
+/// Weights are rescaled before growing past this value, to avoid overflowing.
+const MAX_WEIGHT: f64 = 1e100;
+
+/// Nodes which weight falls below this value when weights are rescaled are dropped, as they are
+/// negligible next to a transaction inserted at the new landmark, so the tree doesn't grow without
+/// limit over an unbounded stream.
+const MIN_WEIGHT: f64 = f64::EPSILON;
+
+/// Mines frequent item sets over a stream, where the contribution of each transaction to the
+/// support of an item set decays exponentially with its age.
+///
+/// A transaction inserted at the timestamp `t` contributes `decay_rate^(now - t)` to the support
+/// of its item sets at the timestamp `now`. Decay is applied lazily: transactions are inserted with
+/// a weight relative to a landmark timestamp, and supports are only brought to the current
+/// timestamp when mining, so the tree doesn't need to be updated as time passes. Transactions
+/// which weight has become negligible are dropped from the tree when weights are rescaled.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::DampedWindow;
+///
+/// let mut window = DampedWindow::new(0.5);
+/// window.insert_transactions(0, &[vec!["A", "B"], vec!["A", "C"]]);
+/// window.insert_transactions(1, &[vec!["B", "C"]]);
+///
+/// // The transactions inserted at 0 have half the weight of the one inserted at 1.
+/// window.mine(1, 1.0, |item_set, support| {
+///     println!("{:?}: {}", item_set, support)
+/// });
+/// ```
+#[derive(Debug)]
+pub struct DampedWindow<'a> {
+    can_tree: WeightedCanTree<'a, f64>,
+    decay_rate: f64,
+    landmark: u64,
+    num_transactions: f64,
+}
+
+impl<'a> DampedWindow<'a> {
+    /// Creates a window where the weight of transactions is multiplied by `decay_rate` for each
+    /// unit of time that passes.
+    ///
+    /// # Panics
+    /// Panics if `decay_rate` is not greater than 0 and less than or equal to 1.
+    pub fn new(decay_rate: f64) -> Self {
+        assert!(
+            decay_rate > 0.0 && decay_rate <= 1.0,
+            "decay_rate must be in (0, 1]"
+        );
+        Self {
+            can_tree: Default::default(),
+            decay_rate,
+            landmark: 0,
+            num_transactions: 0.0,
+        }
+    }
+
+    /// The factor that brings weights relative to the landmark to the provided timestamp.
+    fn decay(&self, timestamp: u64) -> f64 {
+        self.decay_rate
+            .powf(timestamp as f64 - self.landmark as f64)
+    }
+
+    /// Inserts transactions that happened at `timestamp`.
+    pub fn insert_transactions(&mut self, timestamp: u64, transactions: &[Vec<&'a str>]) {
+        let mut weight = 1.0 / self.decay(timestamp);
+        if weight > MAX_WEIGHT {
+            // Move the landmark to the current timestamp.
+            let factor = self.decay(timestamp);
+            self.can_tree.scale(factor, MIN_WEIGHT);
+            self.num_transactions *= factor;
+            self.landmark = timestamp;
+            weight = 1.0;
+        }
+
+        for transaction in transactions {
+            self.can_tree.insert(transaction, weight);
+            self.num_transactions += weight;
+        }
+    }
+
+    /// The number of nodes in the underlying tree. Nodes which weight has decayed to a negligible
+    /// value are dropped when weights are rescaled, which bounds the memory used by the window.
+    pub fn num_nodes(&self) -> usize {
+        self.can_tree.num_nodes()
+    }
+
+    /// The decayed number of transactions at `now`.
+    pub fn num_transactions(&self, now: u64) -> f64 {
+        self.num_transactions * self.decay(now)
+    }
+
+    /// Collects the item sets which decayed support at `now` is at least `min_support`.
+    ///
+    /// # Arguments
+    /// * `now` - the timestamp at which supports are calculated.
+    /// * `min_support` - the minimum decayed support.
+    /// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+    ///   support is found, with its decayed support.
+    pub fn mine<F>(&self, now: u64, min_support: f64, mut collect: F)
+    where
+        F: FnMut(&[&str], f64),
+    {
+        let decay = self.decay(now);
+        self.can_tree
+            .mine(min_support / decay, &mut |item_set, weight| {
+                collect(item_set, weight * decay)
+            });
+    }
+}
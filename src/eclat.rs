@@ -0,0 +1,339 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
+
+/// Sets holding at least one in `DENSE_RATIO` transactions are stored as bitsets, which are
+/// smaller and faster to intersect than lists of ids from that density.
+const DENSE_RATIO: usize = 32;
+
+/// A set of transaction ids, stored either as a sorted list of ids or as a bitset.
+#[derive(Debug, Clone)]
+enum TidSet {
+    Sparse(Vec<usize>),
+    Dense { words: Vec<u64>, len: usize },
+}
+
+impl TidSet {
+    fn sparse(tids: Vec<usize>, num_transactions: usize) -> Self {
+        if tids.len() * DENSE_RATIO < num_transactions {
+            return TidSet::Sparse(tids);
+        }
+        let mut words = vec![0; num_transactions.div_ceil(64)];
+        for tid in &tids {
+            words[tid / 64] |= 1 << (tid % 64);
+        }
+        TidSet::Dense {
+            words,
+            len: tids.len(),
+        }
+    }
+
+    fn dense(words: Vec<u64>, num_transactions: usize) -> Self {
+        let len = words.iter().map(|w| w.count_ones() as usize).sum::<usize>();
+        if len * DENSE_RATIO >= num_transactions {
+            return TidSet::Dense { words, len };
+        }
+        let tids = words
+            .iter()
+            .enumerate()
+            .flat_map(|(index, word)| {
+                (0..64)
+                    .filter(move |bit| word & (1 << bit) != 0)
+                    .map(move |bit| index * 64 + bit)
+            })
+            .collect();
+        TidSet::Sparse(tids)
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            TidSet::Sparse(tids) => tids.len(),
+            TidSet::Dense { len, .. } => *len,
+        }
+    }
+
+    fn contains(words: &[u64], tid: usize) -> bool {
+        words[tid / 64] & (1 << (tid % 64)) != 0
+    }
+
+    /// The ids in both sets.
+    fn intersection(&self, other: &TidSet, num_transactions: usize) -> TidSet {
+        match (self, other) {
+            (TidSet::Sparse(a), TidSet::Sparse(b)) => {
+                TidSet::Sparse(merge(a, b, |in_a, in_b| in_a && in_b))
+            }
+            (TidSet::Sparse(tids), TidSet::Dense { words, .. })
+            | (TidSet::Dense { words, .. }, TidSet::Sparse(tids)) => TidSet::Sparse(
+                tids.iter()
+                    .copied()
+                    .filter(|tid| TidSet::contains(words, *tid))
+                    .collect(),
+            ),
+            (TidSet::Dense { words: a, .. }, TidSet::Dense { words: b, .. }) => TidSet::dense(
+                a.iter().zip(b).map(|(a, b)| a & b).collect(),
+                num_transactions,
+            ),
+        }
+    }
+
+    /// The ids in this set that are not in `other`.
+    fn difference(&self, other: &TidSet, num_transactions: usize) -> TidSet {
+        match (self, other) {
+            (TidSet::Sparse(a), TidSet::Sparse(b)) => {
+                TidSet::Sparse(merge(a, b, |in_a, in_b| in_a && !in_b))
+            }
+            (TidSet::Sparse(tids), TidSet::Dense { words, .. }) => TidSet::Sparse(
+                tids.iter()
+                    .copied()
+                    .filter(|tid| !TidSet::contains(words, *tid))
+                    .collect(),
+            ),
+            (TidSet::Dense { words, .. }, TidSet::Sparse(tids)) => {
+                let mut words = words.clone();
+                for tid in tids {
+                    words[tid / 64] &= !(1 << (tid % 64));
+                }
+                TidSet::dense(words, num_transactions)
+            }
+            (TidSet::Dense { words: a, .. }, TidSet::Dense { words: b, .. }) => TidSet::dense(
+                a.iter().zip(b).map(|(a, b)| a & !b).collect(),
+                num_transactions,
+            ),
+        }
+    }
+}
+
+/// Merges two sorted lists of ids, keeping the ids for which `keep` returns true given whether
+/// they are in each list.
+fn merge(a: &[usize], b: &[usize], keep: impl Fn(bool, bool) -> bool) -> Vec<usize> {
+    let mut result = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        let (tid, in_a, in_b) = match (a.get(i), b.get(j)) {
+            (Some(x), Some(y)) => match x.cmp(y) {
+                Ordering::Less => (*x, true, false),
+                Ordering::Greater => (*y, false, true),
+                Ordering::Equal => (*x, true, true),
+            },
+            (Some(x), None) => (*x, true, false),
+            (None, Some(y)) => (*y, false, true),
+            (None, None) => unreachable!(),
+        };
+        i += in_a as usize;
+        j += in_b as usize;
+        if keep(in_a, in_b) {
+            result.push(tid);
+        }
+    }
+    result
+}
+
+/// An item set in an equivalence class, which all share the same prefix. Depending on the class,
+/// `tids` holds either the transactions containing the item set, or its diffset, which are the
+/// transactions containing the prefix but not the item set.
+struct Member<'a> {
+    item: &'a str,
+    support: usize,
+    tids: TidSet,
+}
+
+struct Miner<'c, F> {
+    min_support: usize,
+    num_transactions: usize,
+    use_diffsets: bool,
+    collect: &'c mut F,
+}
+
+impl<'a, F> Miner<'_, F>
+where
+    F: FnMut(&[&'a str], usize),
+{
+    fn mine_class(&mut self, prefix: &mut Vec<&'a str>, class: &[Member<'a>], diffsets: bool) {
+        for (index, member) in class.iter().enumerate() {
+            prefix.push(member.item);
+            (self.collect)(prefix.as_slice(), member.support);
+
+            // With tidsets, t(PXY) = t(PX) ∩ t(PY). With diffsets, d(PXY) = d(PY) - d(PX), and
+            // the support of PXY is the support of PX minus the size of its diffset.
+            let mut child_class = class[index + 1..]
+                .iter()
+                .map(|other| {
+                    let (tids, support) = if diffsets {
+                        let tids = other.tids.difference(&member.tids, self.num_transactions);
+                        let support = member.support - tids.len();
+                        (tids, support)
+                    } else {
+                        let tids = member.tids.intersection(&other.tids, self.num_transactions);
+                        let support = tids.len();
+                        (tids, support)
+                    };
+                    Member {
+                        item: other.item,
+                        support,
+                        tids,
+                    }
+                })
+                .filter(|m| m.support >= self.min_support)
+                .collect::<Vec<_>>();
+
+            // Diffsets are smaller than tidsets once item sets cover most of their prefix's
+            // transactions, and then stay that way further down.
+            let mut child_diffsets = diffsets;
+            if !diffsets && self.use_diffsets {
+                let tidset_size = child_class.iter().map(|m| m.support).sum::<usize>();
+                let diffset_size = child_class.len() * member.support - tidset_size;
+                if diffset_size < tidset_size {
+                    for child in &mut child_class {
+                        child.tids = member.tids.difference(&child.tids, self.num_transactions);
+                    }
+                    child_diffsets = true;
+                }
+            }
+
+            if !child_class.is_empty() {
+                self.mine_class(prefix, &child_class, child_diffsets);
+            }
+            prefix.pop();
+        }
+    }
+}
+
+fn vertical_mining<'a, F>(
+    transactions: &[Vec<&'a str>],
+    min_support: usize,
+    use_diffsets: bool,
+    collect: &mut F,
+) where
+    F: FnMut(&[&'a str], usize),
+{
+    // Item sets that never occur are not frequent, whatever the minimum support.
+    let min_support = min_support.max(1);
+    let num_transactions = transactions.len();
+    let mut tids = BTreeMap::<&str, Vec<usize>>::new();
+    for (tid, transaction) in transactions.iter().enumerate() {
+        for item in transaction.iter().collect::<HashSet<_>>() {
+            tids.entry(item).or_default().push(tid);
+        }
+    }
+
+    // Least frequent items come first, so the classes of the most frequent ones are small.
+    let mut class = tids
+        .into_iter()
+        .filter(|(_, tids)| tids.len() >= min_support)
+        .map(|(item, tids)| Member {
+            item,
+            support: tids.len(),
+            tids: TidSet::sparse(tids, num_transactions),
+        })
+        .collect::<Vec<_>>();
+    class.sort_by_key(|m| m.support);
+
+    let mut miner = Miner {
+        min_support,
+        num_transactions,
+        use_diffsets,
+        collect,
+    };
+    miner.mine_class(&mut vec![], &class, false);
+}
+
+/// Collects frequent item sets from the provided transactions, using the Eclat algorithm.
+///
+/// Eclat mines a vertical layout of the transactions, where each item set is stored with the set
+/// of transactions containing it, its tidset. The tidset of an item set is the intersection of
+/// the tidsets of two of its subsets, so supports are found without scanning the transactions
+/// again. Tidsets are stored as bitsets when they are dense, and as lists of ids otherwise.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+/// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+///   support is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::eclat;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// eclat(transactions.as_slice(), 3, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+pub fn eclat<F>(transactions: &[Vec<&str>], min_support: usize, mut collect: F)
+where
+    F: FnMut(&[&str], usize),
+{
+    vertical_mining(transactions, min_support, false, &mut collect);
+}
+
+/// Collects frequent item sets from the provided transactions, using the dEclat algorithm.
+///
+/// dEclat works as [`eclat`], but switches from tidsets to diffsets once they are smaller. The
+/// diffset of an item set is the set of transactions that contain its prefix but not the item set
+/// itself, which are small on dense datasets where most item sets are frequent.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+/// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+///   support is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::declat;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// declat(transactions.as_slice(), 3, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+pub fn declat<F>(transactions: &[Vec<&str>], min_support: usize, mut collect: F)
+where
+    F: FnMut(&[&str], usize),
+{
+    vertical_mining(transactions, min_support, true, &mut collect);
+}
+
+/// The density of the transactions, which is the average fraction of the distinct items that a
+/// transaction contains.
+pub(crate) fn density(transactions: &[Vec<&str>]) -> f64 {
+    let items = transactions.iter().flatten().collect::<HashSet<_>>();
+    if items.is_empty() {
+        return 0.0;
+    }
+    let total_items = transactions.iter().map(|t| t.len()).sum::<usize>();
+    total_items as f64 / (transactions.len() * items.len()) as f64
+}
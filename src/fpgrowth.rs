@@ -12,25 +12,68 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Ordering;
+use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{AddAssign, SubAssign};
+
+/// The type used to count occurences of items in a tree. Counts are usually integers, but can be
+/// weights when transactions don't all count the same.
+pub trait Weight: Copy + Default + PartialOrd + AddAssign + SubAssign + Debug {}
+
+impl Weight for usize {}
+impl Weight for f64 {}
+
+/// Orders items from the most to the least frequent, which is the order items have in the paths of
+/// an FP-Tree.
+fn compare_items<W: Weight>(frequencies: &BTreeMap<&str, W>, a: &str, b: &str) -> Ordering {
+    let a_freq = frequencies.get(a).unwrap();
+    let b_freq = frequencies.get(b).unwrap();
+    let cmp = b_freq.partial_cmp(a_freq).unwrap_or(Ordering::Equal);
+    if Ordering::Equal == cmp {
+        return a.cmp(b);
+    }
+    cmp
+}
+
+/// The key of the nodes of a tree, which identifies an item, and how much the transactions going
+/// through a node count towards the support of its item.
+pub(crate) trait NodeKey<'a, W>: Copy + Ord + Debug {
+    fn item(&self) -> &'a str;
+
+    /// The support of the item for `count` transactions going through a node with this key.
+    fn support(&self, count: W) -> W {
+        count
+    }
+}
+
+impl<'a, W> NodeKey<'a, W> for &'a str {
+    fn item(&self) -> &'a str {
+        self
+    }
+}
 
 #[derive(Debug)]
-pub(crate) struct FpNode<'a> {
-    key: &'a str,
-    occurences: usize,
-    children: BTreeMap<&'a str, FpNode<'a>>,
+pub(crate) struct FpNode<'a, W = usize, K = &'a str> {
+    key: K,
+    occurences: W,
+    children: BTreeMap<K, FpNode<'a, W, K>>,
+    items: PhantomData<&'a str>,
 }
 
-impl<'a> FpNode<'a> {
-    pub fn new(key: &'a str) -> Self {
+impl<'a, W: Weight, K: NodeKey<'a, W>> FpNode<'a, W, K> {
+    pub fn new(key: K) -> Self {
         Self {
             key,
-            occurences: 0,
+            occurences: W::default(),
             children: Default::default(),
+            items: PhantomData,
         }
     }
 
-    pub fn add(&mut self, transaction: &[&'a str], count: usize) {
+    pub fn add(&mut self, transaction: &[K], count: W) {
         if transaction[0] != self.key {
             panic!()
         }
@@ -46,13 +89,78 @@ impl<'a> FpNode<'a> {
             .add(&transaction[1..], count);
     }
 
-    pub fn build_conditional_tree(
-        &self,
-        item: &str,
-        mut path: Vec<&'a str>,
-    ) -> Vec<(Vec<&'a str>, usize)> {
-        if self.key == item {
-            return vec![(path, self.occurences)];
+    /// Whether `count` transactions with the items of `transaction`, in the order they have in
+    /// the tree, were added at this node. Paths shared with longer transactions don't count.
+    pub fn contains(&self, transaction: &[K], count: W) -> bool {
+        if transaction[0] != self.key {
+            return false;
+        }
+        if transaction.len() == 1 {
+            let mut ending = self.occurences;
+            for child in self.children.values() {
+                ending -= child.occurences;
+            }
+            return ending >= count;
+        }
+        self.children
+            .get(&transaction[1])
+            .is_some_and(|child| child.contains(&transaction[1..], count))
+    }
+
+    /// Removes a transaction that was previously added, dropping the nodes left without
+    /// occurences.
+    ///
+    /// # Panics
+    /// Panics if `count` transactions with these items were not added at this node.
+    pub fn remove(&mut self, transaction: &[K], count: W) {
+        assert!(
+            self.contains(transaction, count),
+            "transaction was not added to the tree"
+        );
+        self.remove_path(transaction, count);
+    }
+
+    fn remove_path(&mut self, transaction: &[K], count: W) {
+        self.occurences -= count;
+
+        if transaction.len() == 1 {
+            return;
+        }
+
+        let child = self.children.get_mut(&transaction[1]).unwrap();
+        child.remove_path(&transaction[1..], count);
+        if child.occurences == W::default() {
+            self.children.remove(&transaction[1]);
+        }
+    }
+
+    pub fn occurences(&self) -> W {
+        self.occurences
+    }
+
+    /// Adds the paths of another node with the same key, and which paths have their items in the
+    /// same order, to the paths of this node.
+    pub fn merge(&mut self, other: FpNode<'a, W, K>) {
+        self.occurences += other.occurences;
+        merge_nodes(&mut self.children, other.children);
+    }
+
+    /// The number of nodes in the subtree starting at this node.
+    pub fn num_nodes(&self) -> usize {
+        1 + self.children.values().map(FpNode::num_nodes).sum::<usize>()
+    }
+
+    /// Adds the occurences of this node and its descendants to the frequencies of their items.
+    pub fn count_frequencies(&self, frequencies: &mut BTreeMap<&'a str, W>) {
+        *frequencies.entry(self.key.item()).or_default() += self.occurences;
+        for child in self.children.values() {
+            child.count_frequencies(frequencies);
+        }
+    }
+
+    pub fn build_conditional_tree(&self, item: &str, mut path: Vec<K>) -> Vec<(Vec<K>, W)> {
+        if self.key.item() == item {
+            return vec![(path, self.key.support(self.occurences))];
         }
 
         let mut lists = vec![];
@@ -64,11 +172,62 @@ impl<'a> FpNode<'a> {
     }
 }
 
+impl<W: Weight> FpNode<'_, W> {
+    /// Counts the occurences of the item set in the paths starting at this node. Items are
+    /// expected in the order they have in the tree.
+    fn count(&self, item_set: &[&str], frequencies: &BTreeMap<&str, W>) -> W {
+        let item_set = match item_set.first() {
+            Some(item) if *item == self.key => &item_set[1..],
+            // Paths are sorted, so an item that comes before this node won't be found below it.
+            Some(item) if compare_items(frequencies, item, self.key) == Ordering::Less => {
+                return W::default();
+            }
+            _ => item_set,
+        };
+        if item_set.is_empty() {
+            return self.occurences;
+        }
+
+        let mut count = W::default();
+        for child in self.children.values() {
+            count += child.count(item_set, frequencies);
+        }
+        count
+    }
+}
+
+impl<'a> FpNode<'a, f64> {
+    /// Multiplies the occurences of this node and its descendants by `factor`, dropping the
+    /// descendants left with less than `min_occurences`.
+    pub fn scale(&mut self, factor: f64, min_occurences: f64) {
+        self.occurences *= factor;
+        self.children.retain(|_, child| {
+            child.scale(factor, min_occurences);
+            child.occurences >= min_occurences
+        });
+    }
+}
+
+/// Merges the nodes in `other` into the nodes in `into`, which share the same parent.
+pub(crate) fn merge_nodes<'a, W: Weight, K: NodeKey<'a, W>>(
+    into: &mut BTreeMap<K, FpNode<'a, W, K>>,
+    other: BTreeMap<K, FpNode<'a, W, K>>,
+) {
+    for (key, node) in other {
+        match into.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut().merge(node),
+            Entry::Vacant(entry) => {
+                entry.insert(node);
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
-pub(crate) struct FpTree<'a> {
-    pub(crate) root: BTreeMap<&'a str, FpNode<'a>>,
-    pub(crate) frequencies: BTreeMap<&'a str, usize>,
-    pub(crate) min_support: usize,
+pub(crate) struct FpTree<'a, W = usize, K = &'a str> {
+    pub(crate) root: BTreeMap<K, FpNode<'a, W, K>>,
+    pub(crate) frequencies: BTreeMap<&'a str, W>,
+    pub(crate) min_support: W,
 }
 
 impl<'a> FpTree<'a> {
@@ -79,26 +238,48 @@ impl<'a> FpTree<'a> {
             .collect::<Vec<_>>();
         FpTree::build_fp_tree(transactions.as_slice(), min_support)
     }
+}
+
+impl<'a, W: Weight, K: NodeKey<'a, W>> FpTree<'a, W, K> {
+    /// Builds a tree from transactions that may be repeated, each paired with its count.
+    pub(crate) fn build_fp_tree(transactions: &[(Vec<K>, W)], min_support: W) -> FpTree<'a, W, K> {
+        let frequencies = FpTree::count_frequencies(transactions);
+        let root = FpTree::build_root(transactions, &frequencies, min_support);
+        FpTree {
+            root,
+            frequencies,
+            min_support,
+        }
+    }
 
-    fn build_fp_tree(transactions: &[(Vec<&'a str>, usize)], min_support: usize) -> FpTree<'a> {
-        // Build Frequency Lists (F-List)
+    /// Builds the Frequency List (F-List) of the transactions.
+    pub(crate) fn count_frequencies(transactions: &[(Vec<K>, W)]) -> BTreeMap<&'a str, W> {
         let mut frequencies = BTreeMap::new();
         for (items, occurance) in transactions {
             for item in items {
+                let support = item.support(*occurance);
                 frequencies
-                    .entry(*item)
-                    .and_modify(|count| *count += occurance)
-                    .or_insert(*occurance);
+                    .entry(item.item())
+                    .and_modify(|count| *count += support)
+                    .or_insert(support);
             }
         }
+        frequencies
+    }
 
-        // Build the FP-Tree
+    /// Builds the nodes of the tree, with the items of the transactions sorted by `frequencies`,
+    /// which may have been counted on a larger set of transactions.
+    pub(crate) fn build_root(
+        transactions: &[(Vec<K>, W)],
+        frequencies: &BTreeMap<&'a str, W>,
+        min_support: W,
+    ) -> BTreeMap<K, FpNode<'a, W, K>> {
         let mut root = BTreeMap::new();
         for (transaction, count) in transactions {
             // Filter out items which frequency is below min_support.
             let mut transaction = transaction
                 .iter()
-                .filter(|item| *frequencies.get(*item).unwrap() >= min_support)
+                .filter(|item| *frequencies.get(item.item()).unwrap() >= min_support)
                 .copied()
                 .collect::<Vec<_>>();
             if transaction.is_empty() {
@@ -106,30 +287,23 @@ impl<'a> FpTree<'a> {
             }
 
             // Sort transaction by item frequency.
-            transaction.sort_by(|a, b| {
-                let a_freq = frequencies.get(a).unwrap();
-                let b_freq = frequencies.get(b).unwrap();
-                let cmp = b_freq.cmp(a_freq);
-                if std::cmp::Ordering::Equal == cmp {
-                    return a.cmp(b);
-                }
-                cmp
-            });
+            transaction.sort_by(|a, b| compare_items(frequencies, a.item(), b.item()));
 
             // Append transaction to the tree root.
             root.entry(transaction[0])
                 .or_insert_with(|| FpNode::new(transaction[0]))
                 .add(transaction.as_slice(), *count);
         }
+        root
+    }
 
-        FpTree {
-            root,
-            frequencies,
-            min_support,
-        }
+    /// The number of nodes in the tree, not counting the root.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn num_nodes(&self) -> usize {
+        self.root.values().map(FpNode::num_nodes).sum()
     }
 
-    pub(crate) fn build_conditional_tree(&self, item: &str) -> FpTree<'a> {
+    pub(crate) fn build_conditional_tree(&self, item: &str) -> FpTree<'a, W, K> {
         // Build transactions...
         let mut lists = vec![];
         let path = Vec::new();
@@ -141,9 +315,36 @@ impl<'a> FpTree<'a> {
     }
 }
 
-fn fp_growth_tree<F>(fp_tree: &FpTree, collect: &mut F, path: Vec<&str>)
-where
-    F: FnMut(&[&str], usize),
+impl<W: Weight> FpTree<'_, W> {
+    /// Counts the transactions in the tree that contain every item in the item set. Only exact
+    /// for item sets which items were not filtered out when building the tree.
+    pub(crate) fn support(&self, item_set: &[&str]) -> W {
+        if item_set
+            .iter()
+            .any(|item| !self.frequencies.contains_key(item))
+        {
+            return W::default();
+        }
+        let mut item_set = item_set.to_vec();
+        item_set.sort_by(|a, b| compare_items(&self.frequencies, a, b));
+        item_set.dedup();
+
+        let mut count = W::default();
+        for node in self.root.values() {
+            count += node.count(item_set.as_slice(), &self.frequencies);
+        }
+        count
+    }
+}
+
+pub(crate) fn fp_growth_tree<'a, W, K, F>(
+    fp_tree: &FpTree<'a, W, K>,
+    collect: &mut F,
+    path: Vec<&'a str>,
+) where
+    W: Weight,
+    K: NodeKey<'a, W>,
+    F: FnMut(&[&'a str], W),
 {
     // TODO: this should be from less frequent to most frequent.
     for item in fp_tree.frequencies.keys() {
@@ -160,13 +361,126 @@ where
     }
 }
 
+/// A conditional tree being mined by [`FrequentItemSets`], with the item set it is conditioned
+/// on.
+#[derive(Debug)]
+struct Frame<'a> {
+    fp_tree: FpTree<'a>,
+    items: Vec<(&'a str, usize)>,
+    next: usize,
+    path: Vec<&'a str>,
+}
+
+impl<'a> Frame<'a> {
+    fn new(fp_tree: FpTree<'a>, path: Vec<&'a str>) -> Self {
+        let items = fp_tree
+            .frequencies
+            .iter()
+            .filter(|(_, frequency)| **frequency >= fp_tree.min_support)
+            .map(|(item, frequency)| (*item, *frequency))
+            .collect();
+        Frame {
+            fp_tree,
+            items,
+            next: 0,
+            path,
+        }
+    }
+}
+
+/// An iterator over frequent item sets and their occurences, created by [`fp_growth_iter`].
+///
+/// Item sets are mined lazily, as the iterator is advanced, and come in the same order as the
+/// ones reported by [`fp_growth`].
+#[derive(Debug)]
+pub struct FrequentItemSets<'a> {
+    stack: Vec<Frame<'a>>,
+    // The last item set returned, which conditional tree is only built when the next one is
+    // requested.
+    expand: Option<Vec<&'a str>>,
+}
+
+impl<'a> FrequentItemSets<'a> {
+    /// Iterates over the item sets of `fp_tree`, each prefixed by `path`.
+    pub(crate) fn new(fp_tree: FpTree<'a>, path: Vec<&'a str>) -> Self {
+        FrequentItemSets {
+            stack: vec![Frame::new(fp_tree, path)],
+            expand: None,
+        }
+    }
+}
+
+impl<'a> Iterator for FrequentItemSets<'a> {
+    type Item = (Vec<&'a str>, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(path) = self.expand.take() {
+            let frame = self.stack.last().unwrap();
+            let conditional_tree = frame.fp_tree.build_conditional_tree(path.last().unwrap());
+            self.stack.push(Frame::new(conditional_tree, path));
+        }
+
+        while let Some(frame) = self.stack.last_mut() {
+            match frame.items.get(frame.next) {
+                Some((item, frequency)) => {
+                    frame.next += 1;
+                    let mut path = frame.path.clone();
+                    path.push(item);
+                    self.expand = Some(path.clone());
+                    return Some((path, *frequency));
+                }
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Returns an iterator over the frequent item sets of the provided transactions, with their
+/// occurences.
+///
+/// The FP-Tree of the transactions is built upfront, but item sets are mined as the iterator is
+/// advanced, so mining can be paused, or stopped early with adapters such as `take`.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::fp_growth_iter;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// for (item_set, occurences) in fp_growth_iter(transactions.as_slice(), 3).take(5) {
+///     println!("{:?}: {}", item_set, occurences)
+/// }
+/// ```
+pub fn fp_growth_iter<'a>(
+    transactions: &[Vec<&'a str>],
+    min_support: usize,
+) -> FrequentItemSets<'a> {
+    FrequentItemSets::new(FpTree::new(transactions, min_support), vec![])
+}
+
 /// Collects frequent item sets from the provided transactions.
 ///
 /// # Arguments
 /// * `transactions` - a list of transactions.
 /// * `min_support` - the minimum support.
 /// * `collect` - a closure that will be invoked when a new item set that matches the minimum
-///               support is found.
+///   support is found.
 ///
 /// # Example
 /// ```
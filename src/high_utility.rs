@@ -0,0 +1,106 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::fpgrowth::{fp_growth_tree, FpTree};
+
+/// Collects high utility item sets from the provided transactions.
+///
+/// The utility of an item in a transaction is its quantity multiplied by its unit profit, and the
+/// utility of an item set is the sum of the utilities of its items in every transaction that
+/// contains all of them.
+///
+/// Mining happens in two phases. First, an FP-Tree is built where each transaction is weighted by
+/// its total utility, so mining it yields every item set whose Transaction-Weighted Utilization
+/// (TWU) reaches `min_utility`. As the TWU is an upper bound of the utility, this is a superset of
+/// the high utility item sets. Then, the exact utility of each candidate is computed with a scan of
+/// the transactions.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions, where each item is paired with its quantity.
+/// * `profits` - the unit profit for each item. Items missing from this table have no profit.
+/// * `min_utility` - the minimum utility.
+/// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+///   utility is found.
+///
+/// # Example
+/// ```
+/// use std::collections::HashMap;
+/// use fpgrowth_rs::high_utility_item_sets;
+///
+/// let transactions = vec![
+///     vec![("bread", 2), ("butter", 1)],
+///     vec![("bread", 1), ("wine", 1)],
+///     vec![("wine", 2), ("cheese", 1)],
+/// ];
+/// let profits = HashMap::from([("bread", 1), ("butter", 2), ("wine", 10), ("cheese", 5)]);
+///
+/// high_utility_item_sets(transactions.as_slice(), &profits, 20, |item_set, utility| {
+///     println!("{:?}: {}", item_set, utility)
+/// });
+/// ```
+pub fn high_utility_item_sets<F>(
+    transactions: &[Vec<(&str, usize)>],
+    profits: &HashMap<&str, usize>,
+    min_utility: usize,
+    mut collect: F,
+) where
+    F: FnMut(&[&str], usize),
+{
+    // Utility of each item in each transaction. Repeated items have their quantities added up.
+    let utilities = transactions
+        .iter()
+        .map(|transaction| {
+            let mut utilities = HashMap::new();
+            for (item, quantity) in transaction {
+                let profit = profits.get(item).copied().unwrap_or_default();
+                *utilities.entry(*item).or_insert(0) += quantity * profit;
+            }
+            utilities
+        })
+        .collect::<Vec<_>>();
+
+    // Phase 1: Weight each transaction by its utility, and mine the item sets by their TWU.
+    let weighted_transactions = utilities
+        .iter()
+        .map(|utilities| {
+            let mut items = utilities.keys().copied().collect::<Vec<_>>();
+            items.sort();
+            (items, utilities.values().sum::<usize>())
+        })
+        .filter(|(_, transaction_utility)| *transaction_utility > 0)
+        .collect::<Vec<_>>();
+    let fp_tree = FpTree::build_fp_tree(weighted_transactions.as_slice(), min_utility);
+
+    // Phase 2: Calculate the actual utility of each candidate.
+    fp_growth_tree(
+        &fp_tree,
+        &mut |item_set, _| {
+            let utility = utilities
+                .iter()
+                .filter_map(|utilities| {
+                    item_set
+                        .iter()
+                        .map(|item| utilities.get(item))
+                        .sum::<Option<usize>>()
+                })
+                .sum::<usize>();
+            if utility >= min_utility {
+                collect(item_set, utility);
+            }
+        },
+        vec![],
+    );
+}
@@ -0,0 +1,216 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+use crate::fpgrowth::{fp_growth_tree, merge_nodes, FpNode, FpTree, Weight};
+
+/// A Canonical-Order Tree (CanTree), which can be updated as new transactions arrive.
+///
+/// An FP-Tree sorts the items of each transaction by their frequency, so adding transactions to it
+/// may change the order of items and requires rebuilding the tree. A CanTree sorts items in
+/// lexicographic order instead, and keeps every item regardless of its frequency, so new
+/// transactions can be appended to it, and it can be mined with any minimum support.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::CanTree;
+///
+/// let mut can_tree = CanTree::new();
+/// can_tree.insert_transactions(&[vec!["E", "A", "D", "B"], vec!["D", "A", "C", "E", "B"]]);
+/// can_tree.insert_transactions(&[vec!["C", "A", "B", "E"], vec!["B", "A", "D"]]);
+///
+/// can_tree.mine(3, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+#[derive(Debug, Default)]
+pub struct CanTree<'a> {
+    tree: WeightedCanTree<'a, usize>,
+}
+
+impl<'a> CanTree<'a> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The number of transactions inserted in the tree.
+    pub fn num_transactions(&self) -> usize {
+        self.tree.num_transactions
+    }
+
+    /// Appends transactions to the tree.
+    pub fn insert_transactions(&mut self, transactions: &[Vec<&'a str>]) {
+        for transaction in transactions {
+            self.tree.insert(transaction, 1);
+        }
+    }
+
+    /// Removes transactions that were previously inserted in the tree.
+    ///
+    /// # Panics
+    /// Panics if a transaction was not inserted in the tree.
+    pub fn remove_transactions(&mut self, transactions: &[Vec<&'a str>]) {
+        for transaction in transactions {
+            self.tree.remove(transaction, 1);
+        }
+    }
+
+    /// Adds the transactions of another tree to this one. Trees for separate shards of the
+    /// transactions can be built independently, for instance on different threads, and then
+    /// merged into a tree for all of them.
+    ///
+    /// # Example
+    /// ```
+    /// use fpgrowth_rs::CanTree;
+    ///
+    /// let mut can_tree = CanTree::new();
+    /// can_tree.insert_transactions(&[vec!["E", "A", "D", "B"], vec!["D", "A", "C", "E", "B"]]);
+    ///
+    /// let mut shard = CanTree::new();
+    /// shard.insert_transactions(&[vec!["C", "A", "B", "E"], vec!["B", "A", "D"]]);
+    ///
+    /// can_tree.merge(shard);
+    /// assert_eq!(can_tree.num_transactions(), 4);
+    /// ```
+    pub fn merge(&mut self, other: CanTree<'a>) {
+        self.tree.merge(other.tree);
+    }
+
+    /// Collects frequent item sets from the transactions in the tree. Gives the same results as
+    /// calling [`fp_growth`](crate::fp_growth) with every transaction inserted so far.
+    ///
+    /// # Arguments
+    /// * `min_support` - the minimum support.
+    /// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+    ///   support is found.
+    pub fn mine<F>(&self, min_support: usize, mut collect: F)
+    where
+        F: FnMut(&[&str], usize),
+    {
+        self.tree.mine(min_support, &mut collect);
+    }
+}
+
+/// A [`CanTree`] where each transaction is inserted with a weight, which the stream miners use to
+/// count transactions that don't all count the same.
+#[derive(Debug, Default)]
+pub(crate) struct WeightedCanTree<'a, W> {
+    root: BTreeMap<&'a str, FpNode<'a, W>>,
+    frequencies: BTreeMap<&'a str, W>,
+    num_transactions: usize,
+}
+
+impl<'a, W: Weight> WeightedCanTree<'a, W> {
+    pub(crate) fn merge(&mut self, other: WeightedCanTree<'a, W>) {
+        self.num_transactions += other.num_transactions;
+        for (item, frequency) in other.frequencies {
+            *self.frequencies.entry(item).or_default() += frequency;
+        }
+        merge_nodes(&mut self.root, other.root);
+    }
+
+    pub(crate) fn insert(&mut self, transaction: &[&'a str], weight: W) {
+        let mut transaction = transaction.to_vec();
+        transaction.sort();
+        transaction.dedup();
+        self.num_transactions += 1;
+        if transaction.is_empty() {
+            return;
+        }
+
+        for item in &transaction {
+            *self.frequencies.entry(*item).or_default() += weight;
+        }
+        self.root
+            .entry(transaction[0])
+            .or_insert_with(|| FpNode::new(transaction[0]))
+            .add(transaction.as_slice(), weight);
+    }
+
+    pub(crate) fn remove(&mut self, transaction: &[&'a str], weight: W) {
+        let mut transaction = transaction.to_vec();
+        transaction.sort();
+        transaction.dedup();
+        assert!(
+            self.num_transactions > 0,
+            "transaction was not inserted in the tree"
+        );
+
+        // The path is checked before anything is removed, so the tree is left untouched when the
+        // transaction is missing.
+        if !transaction.is_empty() {
+            let node = self
+                .root
+                .get_mut(transaction[0])
+                .expect("transaction was not inserted in the tree");
+            node.remove(transaction.as_slice(), weight);
+            if node.occurences() == W::default() {
+                self.root.remove(transaction[0]);
+            }
+            for item in &transaction {
+                let frequency = self.frequencies.get_mut(item).unwrap();
+                *frequency -= weight;
+                if *frequency == W::default() {
+                    self.frequencies.remove(item);
+                }
+            }
+        }
+        self.num_transactions -= 1;
+    }
+
+    pub(crate) fn mine<F>(&self, min_support: W, collect: &mut F)
+    where
+        F: FnMut(&[&str], W),
+    {
+        for (item, frequency) in &self.frequencies {
+            if *frequency < min_support {
+                continue;
+            }
+            let path = vec![*item];
+            collect(path.as_slice(), *frequency);
+
+            // Items are in canonical order, so the paths leading to an item only contain the items
+            // before it, and each item set is only found once.
+            let mut lists = vec![];
+            for node in self.root.values() {
+                lists.extend(node.build_conditional_tree(item, vec![]));
+            }
+            let conditional_tree = FpTree::build_fp_tree(lists.as_slice(), min_support);
+            fp_growth_tree(&conditional_tree, collect, path);
+        }
+    }
+}
+
+impl<'a> WeightedCanTree<'a, f64> {
+    /// Multiplies the weight of every transaction in the tree by `factor`, dropping the nodes
+    /// left with a weight below `min_weight`.
+    pub(crate) fn scale(&mut self, factor: f64, min_weight: f64) {
+        self.root.retain(|_, node| {
+            node.scale(factor, min_weight);
+            node.occurences() >= min_weight
+        });
+
+        // Frequencies are counted again, as the dropped nodes no longer count towards them.
+        self.frequencies.clear();
+        for node in self.root.values() {
+            node.count_frequencies(&mut self.frequencies);
+        }
+    }
+
+    /// The number of nodes in the tree, not counting the root.
+    pub(crate) fn num_nodes(&self) -> usize {
+        self.root.values().map(FpNode::num_nodes).sum()
+    }
+}
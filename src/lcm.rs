@@ -0,0 +1,239 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// A transaction of a reduced database, standing for `weight` identical transactions. Items are
+/// sorted ids, and items smaller than the core item of the database are only kept when they were
+/// in every merged transaction.
+#[derive(Debug)]
+struct Transaction {
+    items: Vec<usize>,
+    weight: usize,
+}
+
+/// Builds the database of the transactions in `occurences`, keeping only the items in `keep`.
+///
+/// Transactions with the same items above `core` are merged into one, which weight is the sum of
+/// theirs and which items below `core` are the intersection of theirs. Transactions left without
+/// items above `core` can't be extended, and are dropped.
+fn reduce(
+    transactions: &[&Transaction],
+    keep: impl Fn(usize) -> bool,
+    core: Option<usize>,
+) -> Vec<Transaction> {
+    let mut merged: BTreeMap<Vec<usize>, (Vec<usize>, usize)> = BTreeMap::new();
+    for transaction in transactions {
+        let (prefix, suffix): (Vec<_>, Vec<_>) = transaction
+            .items
+            .iter()
+            .copied()
+            .filter(|item| keep(*item))
+            .partition(|item| core.is_some_and(|core| *item < core));
+        if suffix.is_empty() {
+            continue;
+        }
+        merged
+            .entry(suffix)
+            .and_modify(|(merged_prefix, weight)| {
+                merged_prefix.retain(|item| prefix.contains(item));
+                *weight += transaction.weight;
+            })
+            .or_insert((prefix, transaction.weight));
+    }
+    merged
+        .into_iter()
+        .map(|(suffix, (mut items, weight))| {
+            items.extend(suffix);
+            Transaction { items, weight }
+        })
+        .collect()
+}
+
+struct Miner<'a, 'c, F> {
+    items: Vec<&'a str>,
+    min_support: usize,
+    collect: &'c mut F,
+}
+
+impl<'a, F> Miner<'a, '_, F>
+where
+    F: FnMut(&[&'a str], usize),
+{
+    fn report(&mut self, item_set: &[usize], support: usize) {
+        let mut item_set = item_set.iter().map(|i| self.items[*i]).collect::<Vec<_>>();
+        item_set.sort();
+        (self.collect)(item_set.as_slice(), support);
+    }
+
+    /// Extends the closed item set `closed`, which database is `database`, with the items from
+    /// `start` on.
+    fn mine(&mut self, database: &[Transaction], closed: &mut Vec<usize>, start: usize) {
+        // Occurrence deliver: a single scan finds the transactions containing each item.
+        let mut occurences = vec![vec![]; self.items.len()];
+        let mut frequencies = vec![0; self.items.len()];
+        for transaction in database {
+            for item in &transaction.items {
+                if *item >= start {
+                    occurences[*item].push(transaction);
+                    frequencies[*item] += transaction.weight;
+                }
+            }
+        }
+
+        let mut counts = vec![0; self.items.len()];
+        for item in start..self.items.len() {
+            let support = frequencies[item];
+            if support < self.min_support {
+                continue;
+            }
+
+            // The closure of the extension holds the items in every transaction containing it.
+            for transaction in &occurences[item] {
+                for i in &transaction.items {
+                    counts[*i] += transaction.weight;
+                }
+            }
+            let closure = occurences[item][0]
+                .items
+                .iter()
+                .copied()
+                .filter(|i| counts[*i] == support)
+                .collect::<Vec<_>>();
+
+            // Prefix preserving closure extension: the closure is only explored from the
+            // extension adding its smallest new item, so each closed item set is found once.
+            if closure.iter().all(|i| *i >= item) {
+                let length = closed.len();
+                closed.extend(&closure);
+                self.report(closed, support);
+                let database = reduce(
+                    &occurences[item],
+                    |i| counts[i] >= self.min_support && counts[i] != support,
+                    Some(item),
+                );
+                if !database.is_empty() {
+                    self.mine(&database, closed, item + 1);
+                }
+                closed.truncate(length);
+            }
+
+            for transaction in &occurences[item] {
+                for i in &transaction.items {
+                    counts[*i] = 0;
+                }
+            }
+        }
+    }
+}
+
+/// Collects closed frequent item sets from the provided transactions, using the LCM algorithm.
+///
+/// An item set is closed when none of its supersets has the same support. Closed item sets are a
+/// lossless summary of the frequent item sets, as the support of any frequent item set is the
+/// support of its smallest closed superset, and there are usually far fewer of them. Unlike
+/// [`fp_growth`](crate::fp_growth), only the closed frequent item sets are reported, with their
+/// items in lexicographic order.
+///
+/// LCM finds each closed item set exactly once, by extending closed item sets with a single item
+/// and taking their closure, as long as the closure preserves the prefix of items before the
+/// extension. The transactions containing each extension are found in a single scan of the
+/// database, and the database is reduced at each step by removing the items that can't be in
+/// further extensions, and merging the transactions that become identical.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+/// * `collect` - a closure that will be invoked when a new closed item set that matches the
+///   minimum support is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::lcm;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// lcm(transactions.as_slice(), 3, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+pub fn lcm<F>(transactions: &[Vec<&str>], min_support: usize, mut collect: F)
+where
+    F: FnMut(&[&str], usize),
+{
+    if transactions.is_empty() || transactions.len() < min_support {
+        return;
+    }
+
+    let mut frequencies = BTreeMap::new();
+    for transaction in transactions {
+        for item in transaction.iter().collect::<HashSet<_>>() {
+            *frequencies.entry(*item).or_insert(0) += 1;
+        }
+    }
+
+    // Frequent items are identified by their rank from the least to the most frequent.
+    let mut items = frequencies
+        .iter()
+        .filter(|(_, frequency)| **frequency >= min_support)
+        .map(|(item, frequency)| (*frequency, *item))
+        .collect::<Vec<_>>();
+    items.sort();
+    let items = items.into_iter().map(|(_, item)| item).collect::<Vec<_>>();
+    let ids = items
+        .iter()
+        .enumerate()
+        .map(|(id, item)| (*item, id))
+        .collect::<HashMap<_, _>>();
+    let database = transactions
+        .iter()
+        .map(|transaction| {
+            let mut items = transaction
+                .iter()
+                .filter_map(|item| ids.get(item).copied())
+                .collect::<Vec<_>>();
+            items.sort();
+            items.dedup();
+            Transaction { items, weight: 1 }
+        })
+        .collect::<Vec<_>>();
+
+    // The closure of the empty item set holds the items in every transaction.
+    let mut closed = (0..items.len())
+        .filter(|item| frequencies[&items[*item]] == transactions.len())
+        .collect::<Vec<_>>();
+    let mut miner = Miner {
+        items,
+        min_support: min_support.max(1),
+        collect: &mut collect,
+    };
+    if !closed.is_empty() {
+        miner.report(&closed, transactions.len());
+    }
+    let database = reduce(
+        &database.iter().collect::<Vec<_>>(),
+        |item| !closed.contains(&item),
+        None,
+    );
+    miner.mine(&database, &mut closed, 0);
+}
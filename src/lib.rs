@@ -12,13 +12,65 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#![forbid(unsafe_code)]
+
+mod apriori;
 mod association_rules;
+mod classifier;
+mod contrast;
+mod damped_window;
+mod eclat;
 pub(crate) mod fpgrowth;
+mod high_utility;
+mod incremental;
+mod lcm;
+mod lossy_counting;
+mod miner;
 #[cfg(feature = "parallel")]
 mod parallel_fpgrowth;
+#[cfg(feature = "parallel")]
+mod pfp;
+mod prefixspan;
+mod rare;
+mod recommender;
+mod sliding_window;
+#[cfg(feature = "async")]
+mod stream;
+mod uncertain;
 
-pub use association_rules::{generate_association_rules, AssociationRule};
-pub use fpgrowth::fp_growth;
+pub use apriori::apriori;
+pub use association_rules::{
+    generate_association_rules, generate_confident_association_rules,
+    generate_negative_association_rules, generate_rare_association_rules, AssociationRule,
+};
+pub use classifier::{generate_class_association_rules, CbaClassifier};
+pub use contrast::{contrast_patterns, ContrastPattern};
+pub use damped_window::DampedWindow;
+pub use eclat::{declat, eclat};
+pub use fpgrowth::{fp_growth, fp_growth_iter, FrequentItemSets};
+pub use high_utility::high_utility_item_sets;
+pub use incremental::CanTree;
+pub use lcm::lcm;
+pub use lossy_counting::LossyCounter;
+pub use miner::{
+    Apriori, AutoMiner, ClosedItemsetMiner, DEclat, Eclat, FpGrowth, FrequentItemsetMiner, Lcm,
+};
+pub use prefixspan::{
+    generate_sequence_rules, prefix_span, prefix_span_with_constraints, SequenceConstraints,
+    SequenceRule,
+};
+pub use rare::{minimal_rare_item_sets, rare_item_sets};
+pub use recommender::{Aggregation, Recommendation, Recommender};
+pub use sliding_window::SlidingWindow;
+pub use uncertain::uf_growth;
 
 #[cfg(feature = "parallel")]
-pub use parallel_fpgrowth::parallel_fp_growth;
+pub use association_rules::par_generate_association_rules;
+#[cfg(feature = "parallel")]
+pub use parallel_fpgrowth::{
+    par_fp_growth_iter, parallel_fp_growth, ItemSetBatch, ParallelFpGrowth,
+};
+#[cfg(feature = "parallel")]
+pub use pfp::{pfp_growth, pfp_growth_top_k};
+#[cfg(feature = "async")]
+pub use stream::{fp_growth_stream, FrequentItemSetStream};
@@ -0,0 +1,213 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::fp_growth;
+
+/// The transactions of a batch as sets, to count the item sets they contain.
+fn item_sets(batch: &[Vec<String>]) -> Vec<HashSet<&str>> {
+    batch
+        .iter()
+        .map(|t| t.iter().map(|i| i.as_str()).collect())
+        .collect()
+}
+
+/// Counts the transactions containing every item of `item_set`.
+fn count(transactions: &[HashSet<&str>], item_set: &[String]) -> usize {
+    transactions
+        .iter()
+        .filter(|t| item_set.iter().all(|i| t.contains(i.as_str())))
+        .count()
+}
+
+/// The count of an item set since it started being tracked, and the maximum number of occurences
+/// that may have been missed before that.
+#[derive(Debug)]
+struct Entry {
+    count: usize,
+    max_error: f64,
+}
+
+/// Approximately mines frequent item sets over an unbounded stream with bounded memory, using the
+/// Lossy Counting algorithm.
+///
+/// Transactions are buffered and processed in batches. Only the item sets that may still be
+/// frequent are tracked, and the ones which count falls below `epsilon` times the number of
+/// transactions seen are dropped. After `N` transactions, mining with a minimum support `s`
+/// guarantees that:
+///  - Every item set which support is at least `s * N` is reported.
+///  - No item set which support is below `(s - epsilon) * N` is reported.
+///  - Reported counts are underestimated by at most `epsilon * N`.
+///
+/// Each batch is mined with [`fp_growth`], with a minimum support of `epsilon` times the size of
+/// the batch, so the batch size should be large compared to `1 / epsilon`.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::LossyCounter;
+///
+/// let mut lossy_counter = LossyCounter::new(0.1, 100);
+/// for _ in 0..100 {
+///     lossy_counter.insert_transactions(&[vec!["A", "B"], vec!["A", "C"], vec!["B", "D"]]);
+/// }
+///
+/// lossy_counter.mine(0.3, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+#[derive(Debug)]
+pub struct LossyCounter {
+    epsilon: f64,
+    batch_size: usize,
+    buffer: Vec<Vec<String>>,
+    entries: HashMap<Vec<String>, Entry>,
+    num_transactions: usize,
+}
+
+impl LossyCounter {
+    /// Creates a counter with the maximum error `epsilon`, relative to the number of transactions,
+    /// that processes transactions in batches of `batch_size`.
+    ///
+    /// # Panics
+    /// Panics if `epsilon` is not between 0 and 1, or if `batch_size` is 0.
+    pub fn new(epsilon: f64, batch_size: usize) -> Self {
+        assert!(epsilon > 0.0 && epsilon < 1.0, "epsilon must be in (0, 1)");
+        assert!(batch_size > 0, "batch_size must be greater than 0");
+        Self {
+            epsilon,
+            batch_size,
+            buffer: Vec::with_capacity(batch_size),
+            entries: HashMap::new(),
+            num_transactions: 0,
+        }
+    }
+
+    /// The number of transactions seen so far.
+    pub fn num_transactions(&self) -> usize {
+        self.num_transactions + self.buffer.len()
+    }
+
+    /// The number of item sets currently being tracked.
+    pub fn num_entries(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Inserts transactions, processing a batch whenever the buffer is full.
+    pub fn insert_transactions(&mut self, transactions: &[Vec<&str>]) {
+        for transaction in transactions {
+            let mut transaction = transaction
+                .iter()
+                .map(|i| i.to_string())
+                .collect::<Vec<_>>();
+            transaction.sort();
+            transaction.dedup();
+            self.buffer.push(transaction);
+            if self.buffer.len() == self.batch_size {
+                self.process_batch();
+            }
+        }
+    }
+
+    fn process_batch(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        let batch = std::mem::take(&mut self.buffer);
+        let previous_error = self.epsilon * self.num_transactions as f64;
+        self.num_transactions += batch.len();
+        let error = self.epsilon * self.num_transactions as f64;
+
+        // Update the counts of the item sets already being tracked.
+        let sets = item_sets(&batch);
+        for (item_set, entry) in self.entries.iter_mut() {
+            entry.count += count(&sets, item_set);
+        }
+
+        // Start tracking the item sets frequent enough in the batch to survive the pruning. As
+        // item sets that are not being tracked occurred at most `previous_error` times before this
+        // batch, that is also the maximum error in their count.
+        let transactions = batch
+            .iter()
+            .map(|t| t.iter().map(|i| i.as_str()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let min_support = (self.epsilon * batch.len() as f64).floor() as usize + 1;
+        let entries = &mut self.entries;
+        fp_growth(
+            transactions.as_slice(),
+            min_support,
+            |item_set, occurences| {
+                let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+                item_set.sort();
+                entries.entry(item_set).or_insert(Entry {
+                    count: occurences,
+                    max_error: previous_error,
+                });
+            },
+        );
+
+        // Stop tracking item sets which may have occurred at most `error` times.
+        self.entries
+            .retain(|_, entry| entry.count as f64 + entry.max_error > error);
+    }
+
+    /// Collects the item sets which support may be at least `min_support` times the number of
+    /// transactions seen so far, with their approximate counts.
+    ///
+    /// Transactions waiting in the buffer are counted too, but are only processed once the batch
+    /// is full, so mining doesn't start tracking new item sets.
+    ///
+    /// # Arguments
+    /// * `min_support` - the minimum support, relative to the number of transactions.
+    /// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+    ///   support is found.
+    pub fn mine<F>(&self, min_support: f64, mut collect: F)
+    where
+        F: FnMut(&[&str], usize),
+    {
+        let num_transactions = self.num_transactions() as f64;
+        let min_count = (min_support - self.epsilon) * num_transactions;
+        let buffered = item_sets(&self.buffer);
+        for (item_set, entry) in &self.entries {
+            let occurences = entry.count + count(&buffered, item_set);
+            if occurences as f64 >= min_count {
+                let item_set = item_set.iter().map(|i| i.as_str()).collect::<Vec<_>>();
+                collect(item_set.as_slice(), occurences);
+            }
+        }
+
+        // Item sets that are not being tracked occurred at most `epsilon` times the number of
+        // processed transactions before the buffer, so only the ones frequent enough in the buffer
+        // may reach the minimum support.
+        let error = self.epsilon * self.num_transactions as f64;
+        let min_buffer_support = (min_support * num_transactions - error).ceil().max(1.0) as usize;
+        let transactions = self
+            .buffer
+            .iter()
+            .map(|t| t.iter().map(|i| i.as_str()).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        fp_growth(
+            transactions.as_slice(),
+            min_buffer_support,
+            |item_set, occurences| {
+                let mut item_set = item_set.to_vec();
+                item_set.sort();
+                let key = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+                if !self.entries.contains_key(&key) && occurences as f64 >= min_count {
+                    collect(item_set.as_slice(), occurences);
+                }
+            },
+        );
+    }
+}
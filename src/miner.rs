@@ -0,0 +1,195 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::eclat::density;
+use crate::{apriori, declat, eclat, fp_growth, lcm};
+
+/// An algorithm that mines frequent item sets, so callers can switch between algorithms.
+///
+/// Every miner reports each frequent item set exactly once, with its number of occurences, but the
+/// order of the item sets, and of the items in them, depends on the algorithm. Algorithms that
+/// only report some of the frequent item sets, such as the closed ones, implement
+/// [`ClosedItemsetMiner`] instead.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::{Apriori, FpGrowth, FrequentItemsetMiner};
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+/// ];
+///
+/// let miners: [&dyn FrequentItemsetMiner; 2] = [&FpGrowth, &Apriori];
+/// for miner in miners {
+///     miner.mine(transactions.as_slice(), 3, &mut |item_set, occurences| {
+///         println!("{:?}: {}", item_set, occurences)
+///     });
+/// }
+/// ```
+pub trait FrequentItemsetMiner {
+    /// Collects frequent item sets from the provided transactions.
+    ///
+    /// # Arguments
+    /// * `transactions` - a list of transactions.
+    /// * `min_support` - the minimum support.
+    /// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+    ///   support is found.
+    fn mine(
+        &self,
+        transactions: &[Vec<&str>],
+        min_support: usize,
+        collect: &mut dyn FnMut(&[&str], usize),
+    );
+}
+
+/// Mines frequent item sets with [`fp_growth`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FpGrowth;
+
+impl FrequentItemsetMiner for FpGrowth {
+    fn mine(
+        &self,
+        transactions: &[Vec<&str>],
+        min_support: usize,
+        collect: &mut dyn FnMut(&[&str], usize),
+    ) {
+        fp_growth(transactions, min_support, collect);
+    }
+}
+
+/// Mines frequent item sets with [`apriori`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Apriori;
+
+impl FrequentItemsetMiner for Apriori {
+    fn mine(
+        &self,
+        transactions: &[Vec<&str>],
+        min_support: usize,
+        collect: &mut dyn FnMut(&[&str], usize),
+    ) {
+        apriori(transactions, min_support, collect);
+    }
+}
+
+/// Mines frequent item sets with [`eclat`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Eclat;
+
+impl FrequentItemsetMiner for Eclat {
+    fn mine(
+        &self,
+        transactions: &[Vec<&str>],
+        min_support: usize,
+        collect: &mut dyn FnMut(&[&str], usize),
+    ) {
+        eclat(transactions, min_support, collect);
+    }
+}
+
+/// Mines frequent item sets with [`declat`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DEclat;
+
+impl FrequentItemsetMiner for DEclat {
+    fn mine(
+        &self,
+        transactions: &[Vec<&str>],
+        min_support: usize,
+        collect: &mut dyn FnMut(&[&str], usize),
+    ) {
+        declat(transactions, min_support, collect);
+    }
+}
+
+/// An algorithm that mines closed frequent item sets, which are the frequent item sets without a
+/// superset of the same support.
+///
+/// Closed item sets are a summary of the frequent item sets, so a closed miner can't stand in for
+/// a [`FrequentItemsetMiner`], but reports its item sets in the same format.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::{ClosedItemsetMiner, Lcm};
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+/// ];
+///
+/// Lcm.mine(transactions.as_slice(), 3, &mut |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+pub trait ClosedItemsetMiner {
+    /// Collects closed frequent item sets from the provided transactions.
+    ///
+    /// # Arguments
+    /// * `transactions` - a list of transactions.
+    /// * `min_support` - the minimum support.
+    /// * `collect` - a closure that will be invoked when a new closed item set that matches the
+    ///   minimum support is found.
+    fn mine(
+        &self,
+        transactions: &[Vec<&str>],
+        min_support: usize,
+        collect: &mut dyn FnMut(&[&str], usize),
+    );
+}
+
+/// Mines closed frequent item sets with [`lcm`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Lcm;
+
+impl ClosedItemsetMiner for Lcm {
+    fn mine(
+        &self,
+        transactions: &[Vec<&str>],
+        min_support: usize,
+        collect: &mut dyn FnMut(&[&str], usize),
+    ) {
+        lcm(transactions, min_support, collect);
+    }
+}
+
+/// The density from which [`AutoMiner`] prefers vertical mining.
+const DENSE_THRESHOLD: f64 = 0.1;
+
+/// Mines frequent item sets with the algorithm best suited to the density of the transactions,
+/// which is the average fraction of the distinct items that a transaction contains.
+///
+/// Dense datasets, where transactions contain at least a tenth of the items, are mined with
+/// [`declat`], and sparse ones with [`fp_growth`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AutoMiner;
+
+impl FrequentItemsetMiner for AutoMiner {
+    fn mine(
+        &self,
+        transactions: &[Vec<&str>],
+        min_support: usize,
+        collect: &mut dyn FnMut(&[&str], usize),
+    ) {
+        if density(transactions) >= DENSE_THRESHOLD {
+            declat(transactions, min_support, collect);
+        } else {
+            fp_growth(transactions, min_support, collect);
+        }
+    }
+}
@@ -12,24 +12,102 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::fpgrowth::{FpNode, FpTree};
-use rayon::Scope;
+use crate::fpgrowth::{fp_growth_tree, merge_nodes, FpTree, FrequentItemSets};
+use rayon::prelude::*;
+use rayon::{Scope, ThreadPool, ThreadPoolBuilder};
+use std::collections::BTreeMap;
 use std::sync::{
-    mpsc::{channel, Sender},
-    Arc,
+    mpsc::{channel, sync_channel, Receiver, Sender, SyncSender},
+    Arc, Mutex,
 };
 
-unsafe impl<'a> Send for FpNode<'a> {}
-unsafe impl<'a> Sync for FpNode<'a> {}
+/// Item sets found by a worker thread of [`ParallelFpGrowth`], with their items stored in a single
+/// buffer.
+#[derive(Debug, Default)]
+pub struct ItemSetBatch<'a> {
+    items: Vec<&'a str>,
+    /// The end of each item set in `items`, and its occurences.
+    item_sets: Vec<(usize, usize)>,
+}
+
+impl<'a> ItemSetBatch<'a> {
+    fn push(&mut self, item_set: &[&'a str], occurences: usize) {
+        self.items.extend_from_slice(item_set);
+        self.item_sets.push((self.items.len(), occurences));
+    }
+
+    /// The number of item sets in the batch.
+    pub fn len(&self) -> usize {
+        self.item_sets.len()
+    }
+
+    /// Whether the batch has no item sets.
+    pub fn is_empty(&self) -> bool {
+        self.item_sets.is_empty()
+    }
+
+    /// Iterates over the item sets in the batch, with their occurences.
+    pub fn iter(&self) -> impl Iterator<Item = (&[&'a str], usize)> + '_ {
+        let starts = std::iter::once(0).chain(self.item_sets.iter().map(|(end, _)| *end));
+        starts
+            .zip(&self.item_sets)
+            .map(|(start, (end, occurences))| (&self.items[start..*end], *occurences))
+    }
+}
+
+/// How the tasks mining the tree deliver the item sets they find.
+struct Delivery<'s, 'a> {
+    granularity: usize,
+    batch_size: usize,
+    /// A batch for each worker thread, filled by the tasks running on it.
+    batches: Vec<Mutex<ItemSetBatch<'a>>>,
+    deliver: &'s (dyn Fn(ItemSetBatch<'a>) + Sync),
+}
+
+impl<'a> Delivery<'_, 'a> {
+    fn push(&self, item_set: &[&'a str], occurences: usize) {
+        let index = rayon::current_thread_index().unwrap_or(0) % self.batches.len();
+        let mut batch = self.batches[index].lock().unwrap();
+        batch.push(item_set, occurences);
+        if batch.len() >= self.batch_size {
+            let full = std::mem::take(&mut *batch);
+            // Delivering may block on the channel, so the batch is released first.
+            drop(batch);
+            (self.deliver)(full);
+        }
+    }
+
+    /// Delivers the item sets left in the batches, once every task is done.
+    fn flush(self) {
+        for batch in self.batches {
+            let batch = batch.into_inner().unwrap();
+            if !batch.is_empty() {
+                (self.deliver)(batch);
+            }
+        }
+    }
+}
 
-unsafe impl<'a> Send for FpTree<'a> {}
-unsafe impl<'a> Sync for FpTree<'a> {}
+/// The sending half of a channel, which is bounded when a capacity is set.
+enum BatchSender<T> {
+    Unbounded(Sender<T>),
+    Bounded(SyncSender<T>),
+}
+
+impl<T> BatchSender<T> {
+    fn send(&self, value: T) {
+        match self {
+            BatchSender::Unbounded(tx) => tx.send(value).unwrap(),
+            BatchSender::Bounded(tx) => tx.send(value).unwrap(),
+        }
+    }
+}
 
-fn handle_item<'a>(
+fn handle_item<'s, 'a: 's>(
+    delivery: &'s Delivery<'s, 'a>,
     fp_tree: Arc<FpTree<'a>>,
     path: Vec<&'a str>,
-    tx: Sender<(Vec<&'a str>, usize)>,
-    s: &Scope<'a>,
+    s: &Scope<'s>,
     item: &'a str,
 ) {
     s.spawn(move |s| {
@@ -38,38 +116,421 @@ fn handle_item<'a>(
                 // Collect the data.
                 let mut path = path.clone();
                 path.push(item);
-                tx.send((path.clone(), *frequency)).unwrap();
+                delivery.push(&path, *frequency);
 
-                // Iterate to children.
+                // Iterate to children, sequentially when the tree is too small to split.
                 let conditional_tree = fp_tree.build_conditional_tree(item);
-                let cloned_path = path.clone();
-                let cloned_tx = tx.clone();
-                paralled_fp_growth_tree(conditional_tree, cloned_path, cloned_tx, s);
+                if delivery.granularity > 0 && conditional_tree.num_nodes() < delivery.granularity {
+                    fp_growth_tree(
+                        &conditional_tree,
+                        &mut |item_set, occurences| delivery.push(item_set, occurences),
+                        path,
+                    );
+                } else {
+                    paralled_fp_growth_tree(delivery, conditional_tree, path, s);
+                }
             }
             _ => (),
         }
     });
 }
-fn paralled_fp_growth_tree<'a>(
+fn paralled_fp_growth_tree<'s, 'a: 's>(
+    delivery: &'s Delivery<'s, 'a>,
     fp_tree: FpTree<'a>,
     path: Vec<&'a str>,
-    tx: Sender<(Vec<&'a str>, usize)>,
-    s: &Scope<'a>,
+    s: &Scope<'s>,
 ) {
     // TODO: this should be from less frequent to most frequent.
     let fp_tree = Arc::new(fp_tree);
     for item in fp_tree.frequencies.keys() {
-        handle_item(fp_tree.clone(), path.clone(), tx.clone(), s, item);
+        handle_item(delivery, fp_tree.clone(), path.clone(), s, item);
+    }
+}
+
+/// Builds an FP-Tree on the current thread pool. Items are counted in parallel, and each shard of
+/// the transactions is added to its own tree, sorting items by their frequency in all the
+/// transactions. The trees of the shards then have their items in the same order, and are merged.
+fn build_fp_tree<'a>(transactions: &[Vec<&'a str>], min_support: usize) -> FpTree<'a> {
+    let shard_size = transactions
+        .len()
+        .div_ceil(rayon::current_num_threads())
+        .max(1);
+    let shards = transactions
+        .par_chunks(shard_size)
+        .map(|shard| {
+            shard
+                .iter()
+                .map(|t| (t.to_vec(), 1_usize))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let frequencies = shards
+        .par_iter()
+        .map(|shard| FpTree::count_frequencies(shard))
+        .reduce(BTreeMap::new, |mut frequencies, other| {
+            for (item, frequency) in other {
+                *frequencies.entry(item).or_default() += frequency;
+            }
+            frequencies
+        });
+    let root = shards
+        .par_iter()
+        .map(|shard| FpTree::build_root(shard, &frequencies, min_support))
+        .reduce(BTreeMap::new, |mut root, other| {
+            merge_nodes(&mut root, other);
+            root
+        });
+    FpTree {
+        root,
+        frequencies,
+        min_support,
+    }
+}
+
+/// The item sets found in the tree, in the order [`fp_growth`](crate::fp_growth) finds them.
+/// Items are mined in parallel, and their results are concatenated in order.
+fn ordered_fp_growth_tree<'a>(
+    granularity: usize,
+    fp_tree: &FpTree<'a>,
+    path: &[&'a str],
+) -> Vec<(Vec<&'a str>, usize)> {
+    frequent_items(fp_tree)
+        .into_par_iter()
+        .map(|(item, frequency)| mine_item(granularity, fp_tree, path, item, frequency))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+fn frequent_items<'a>(fp_tree: &FpTree<'a>) -> Vec<(&'a str, usize)> {
+    fp_tree
+        .frequencies
+        .iter()
+        .filter(|(_, frequency)| **frequency >= fp_tree.min_support)
+        .map(|(item, frequency)| (*item, *frequency))
+        .collect()
+}
+
+fn mine_item<'a>(
+    granularity: usize,
+    fp_tree: &FpTree<'a>,
+    path: &[&'a str],
+    item: &'a str,
+    frequency: usize,
+) -> Vec<(Vec<&'a str>, usize)> {
+    let mut path = path.to_vec();
+    path.push(item);
+    let conditional_tree = fp_tree.build_conditional_tree(item);
+    let mut item_sets = vec![(path.clone(), frequency)];
+    if granularity > 0 && conditional_tree.num_nodes() < granularity {
+        fp_growth_tree(
+            &conditional_tree,
+            &mut |item_set, occurences| item_sets.push((item_set.to_vec(), occurences)),
+            path,
+        );
+    } else {
+        item_sets.extend(ordered_fp_growth_tree(
+            granularity,
+            &conditional_tree,
+            &path,
+        ));
+    }
+    item_sets
+}
+
+/// Runs `f` on the thread pool, or on rayon's global pool.
+fn install<R: Send>(pool: Option<&ThreadPool>, f: impl FnOnce() -> R + Send) -> R {
+    match pool {
+        None => f(),
+        Some(thread_pool) => thread_pool.install(f),
+    }
+}
+
+/// The thread pool a [`ParallelFpGrowth`] runs on.
+#[derive(Debug, Default, Clone)]
+enum Pool {
+    #[default]
+    Global,
+    Shared(Arc<ThreadPool>),
+}
+
+/// A parallel FP-Growth miner, configured with a builder.
+///
+/// By default, item sets are reported in the order they are found by the worker threads, which
+/// changes from run to run. With [`deterministic`](ParallelFpGrowth::deterministic), they are
+/// reported in the same order as [`fp_growth`](crate::fp_growth), with their items in
+/// lexicographic order.
+///
+/// The FP-Tree is also built in parallel, from shards of the transactions. Item sets are sent in
+/// batches to the calling thread, which reports them, so `collect` doesn't need to be `Send`. A
+/// [`channel_capacity`](ParallelFpGrowth::channel_capacity) bounds the memory used by the
+/// batches waiting for a slow `collect`, and [`mine_with_sink`](ParallelFpGrowth::mine_with_sink)
+/// delivers item sets on the worker threads instead.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::ParallelFpGrowth;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// ParallelFpGrowth::new()
+///     .deterministic(true)
+///     .mine(transactions.as_slice(), 3, |item_set, occurences| {
+///         println!("{:?}: {}", item_set, occurences)
+///     });
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParallelFpGrowth {
+    deterministic: bool,
+    pool: Pool,
+    granularity: usize,
+    batch_size: usize,
+    channel_capacity: Option<usize>,
+}
+
+impl Default for ParallelFpGrowth {
+    fn default() -> Self {
+        Self {
+            deterministic: false,
+            pool: Pool::default(),
+            granularity: 0,
+            batch_size: 256,
+            channel_capacity: None,
+        }
+    }
+}
+
+impl ParallelFpGrowth {
+    /// Creates a miner with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether item sets are reported in a deterministic order.
+    ///
+    /// The item sets found under each frequent item of the FP-Tree are still mined in parallel,
+    /// but are buffered until the item sets of all the items before it have been reported.
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Runs on the provided thread pool, instead of rayon's global pool.
+    pub fn thread_pool(mut self, thread_pool: Arc<ThreadPool>) -> Self {
+        self.pool = Pool::Shared(thread_pool);
+        self
+    }
+
+    /// Runs on a new thread pool with `num_threads` threads, instead of rayon's global pool. The
+    /// pool is built once, and shared by every call to [`mine`](ParallelFpGrowth::mine) and by
+    /// the clones of this miner.
+    ///
+    /// # Panics
+    /// Panics if the thread pool can't be built.
+    pub fn num_threads(mut self, num_threads: usize) -> Self {
+        let thread_pool = ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .expect("failed to build the thread pool");
+        self.pool = Pool::Shared(Arc::new(thread_pool));
+        self
+    }
+
+    /// Sets the number of nodes a conditional FP-Tree needs for its items to be mined in
+    /// parallel. Smaller trees are mined sequentially by the thread that built them, which avoids
+    /// spawning a task for each item of the many tiny trees found deep in the recursion. Defaults
+    /// to 0, which mines every tree in parallel.
+    pub fn granularity(mut self, granularity: usize) -> Self {
+        self.granularity = granularity;
+        self
+    }
+
+    /// Sets the number of item sets a worker thread sends at once. Each worker thread buffers the
+    /// item sets found by its tasks, and only sends a smaller batch when mining is over. Larger
+    /// batches reduce the overhead of sending item sets, but delay their delivery. Defaults to 256.
+    ///
+    /// # Panics
+    /// Panics if `batch_size` is 0.
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be greater than 0");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets the number of batches that can wait to be reported. Once it is reached, tasks block
+    /// until `collect` catches up, so memory doesn't grow when it is slower than mining. Defaults
+    /// to no limit.
+    ///
+    /// With [`deterministic`](ParallelFpGrowth::deterministic) ordering, the item sets of each
+    /// top level item are sent as a single batch, and batches received out of order are still
+    /// buffered until they can be reported.
+    pub fn channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.channel_capacity = Some(channel_capacity);
+        self
+    }
+
+    fn channel<T>(&self) -> (BatchSender<T>, Receiver<T>) {
+        match self.channel_capacity {
+            None => {
+                let (tx, rx) = channel();
+                (BatchSender::Unbounded(tx), rx)
+            }
+            Some(capacity) => {
+                let (tx, rx) = sync_channel(capacity);
+                (BatchSender::Bounded(tx), rx)
+            }
+        }
+    }
+
+    /// Mines the tree, passing batches of item sets to `deliver` on the worker threads.
+    fn mine_batches<'a>(
+        &self,
+        pool: Option<&ThreadPool>,
+        fp_tree: FpTree<'a>,
+        deliver: &(dyn Fn(ItemSetBatch<'a>) + Sync),
+    ) {
+        install(pool, || {
+            let delivery = Delivery {
+                granularity: self.granularity,
+                batch_size: self.batch_size,
+                batches: (0..rayon::current_num_threads())
+                    .map(|_| Mutex::default())
+                    .collect(),
+                deliver,
+            };
+            rayon::scope(|s| paralled_fp_growth_tree(&delivery, fp_tree, vec![], s));
+            delivery.flush();
+        });
+    }
+
+    /// The thread pool calls run on, or `None` for rayon's global pool.
+    fn pool(&self) -> Option<&ThreadPool> {
+        match &self.pool {
+            Pool::Global => None,
+            Pool::Shared(thread_pool) => Some(thread_pool),
+        }
+    }
+
+    /// Collects frequent item sets from the provided transactions.
+    ///
+    /// # Arguments
+    /// * `transactions` - a list of transactions.
+    /// * `min_support` - the minimum support.
+    /// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+    ///   support is found.
+    pub fn mine<F>(&self, transactions: &[Vec<&str>], min_support: usize, mut collect: F)
+    where
+        F: FnMut(&[&str], usize),
+    {
+        let pool = self.pool();
+        let fp_tree = install(pool, || build_fp_tree(transactions, min_support));
+        if !self.deterministic {
+            std::thread::scope(|s| {
+                let (tx, rx) = self.channel::<ItemSetBatch>();
+                s.spawn(move || self.mine_batches(pool, fp_tree, &|batch| tx.send(batch)));
+                while let Ok(batch) = rx.recv() {
+                    for (item_set, occurences) in batch.iter() {
+                        collect(item_set, occurences);
+                    }
+                }
+            });
+            return;
+        }
+
+        // Results of each top level item are reported once those of the previous items are.
+        std::thread::scope(|s| {
+            let (tx, rx) = self.channel::<(usize, Vec<(Vec<&str>, usize)>)>();
+            s.spawn(|| {
+                // The sender is dropped once mining is over, which ends the loop below.
+                let tx = tx;
+                install(pool, || {
+                    frequent_items(&fp_tree)
+                        .into_par_iter()
+                        .enumerate()
+                        .for_each(|(index, (item, frequency))| {
+                            let item_sets =
+                                mine_item(self.granularity, &fp_tree, &[], item, frequency);
+                            tx.send((index, item_sets));
+                        })
+                })
+            });
+            let mut pending = BTreeMap::new();
+            let mut next = 0;
+            while let Ok((index, item_sets)) = rx.recv() {
+                pending.insert(index, item_sets);
+                while let Some(item_sets) = pending.remove(&next) {
+                    for (mut item_set, occurences) in item_sets {
+                        item_set.sort();
+                        collect(&item_set, occurences);
+                    }
+                    next += 1;
+                }
+            }
+        });
+    }
+
+    /// Collects frequent item sets from the provided transactions, passing them in batches to
+    /// `sink` on the worker threads as soon as a batch is full, in no particular order regardless
+    /// of [`deterministic`](ParallelFpGrowth::deterministic).
+    ///
+    /// # Arguments
+    /// * `transactions` - a list of transactions.
+    /// * `min_support` - the minimum support.
+    /// * `sink` - a closure that will be invoked, possibly from several threads at once, with each
+    ///   batch of item sets that match the minimum support.
+    ///
+    /// # Example
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    ///
+    /// use fpgrowth_rs::ParallelFpGrowth;
+    ///
+    /// let transactions = vec![
+    ///     vec!["E", "A", "D", "B"],
+    ///     vec!["D", "A", "C", "E", "B"],
+    ///     vec!["C", "A", "B", "E"],
+    ///     vec!["B", "A", "D"],
+    /// ];
+    ///
+    /// let count = AtomicUsize::new(0);
+    /// ParallelFpGrowth::new().mine_with_sink(transactions.as_slice(), 3, |batch| {
+    ///     for (item_set, occurences) in batch.iter() {
+    ///         println!("{:?}: {}", item_set, occurences);
+    ///     }
+    ///     count.fetch_add(batch.len(), Ordering::Relaxed);
+    /// });
+    /// assert_eq!(count.into_inner(), 11);
+    /// ```
+    pub fn mine_with_sink<S>(&self, transactions: &[Vec<&str>], min_support: usize, sink: S)
+    where
+        S: Fn(&ItemSetBatch) + Sync,
+    {
+        let pool = self.pool();
+        let fp_tree = install(pool, || build_fp_tree(transactions, min_support));
+        self.mine_batches(pool, fp_tree, &|batch| sink(&batch));
     }
 }
 
 /// Collects frequent item sets from the provided transactions, using a parallel implementation.
 ///
+/// Item sets are reported in no particular order. Use [`ParallelFpGrowth`] for more options.
+///
 /// # Arguments
 /// * `transactions` - a list of transactions.
 /// * `min_support` - the minimum support.
 /// * `collect` - a closure that will be invoked when a new item set that matches the minimum
-///               support is found.
+///   support is found.
 ///
 /// # Example
 /// ```
@@ -91,16 +552,55 @@ fn paralled_fp_growth_tree<'a>(
 /// });
 ///
 /// ```
-pub fn parallel_fp_growth<F>(transactions: &[Vec<&str>], min_support: usize, mut collect: F)
+pub fn parallel_fp_growth<F>(transactions: &[Vec<&str>], min_support: usize, collect: F)
 where
-    F: FnMut(&[&str], usize) + Send,
+    F: FnMut(&[&str], usize),
 {
-    rayon::scope(|s| {
-        let (tx, rx) = channel::<(Vec<&str>, usize)>();
-        let fp_tree = FpTree::new(transactions, min_support);
-        paralled_fp_growth_tree(fp_tree, vec![], tx, s);
-        while let Ok((a, b)) = rx.recv() {
-            collect(&a, b);
-        }
-    });
+    ParallelFpGrowth::new().mine(transactions, min_support, collect);
+}
+
+/// Returns a parallel iterator over the frequent item sets of the provided transactions, with
+/// their occurences.
+///
+/// The FP-Tree is built in parallel, and the item sets starting with each frequent item are then
+/// mined lazily by [`fp_growth_iter`](crate::fp_growth_iter), with items split across the threads
+/// of the pool the iterator is driven from.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::par_fp_growth_iter;
+/// use rayon::prelude::*;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// let pairs = par_fp_growth_iter(transactions.as_slice(), 3)
+///     .filter(|(item_set, _)| item_set.len() == 2)
+///     .count();
+/// assert_eq!(pairs, 7);
+/// ```
+pub fn par_fp_growth_iter<'a>(
+    transactions: &[Vec<&'a str>],
+    min_support: usize,
+) -> impl ParallelIterator<Item = (Vec<&'a str>, usize)> + 'a {
+    let fp_tree = Arc::new(build_fp_tree(transactions, min_support));
+    frequent_items(&fp_tree)
+        .into_par_iter()
+        .flat_map_iter(move |(item, frequency)| {
+            let conditional_tree = fp_tree.build_conditional_tree(item);
+            std::iter::once((vec![item], frequency))
+                .chain(FrequentItemSets::new(conditional_tree, vec![item]))
+        })
 }
@@ -0,0 +1,287 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::mpsc::channel;
+use std::sync::Mutex;
+
+use rayon::prelude::*;
+
+use crate::fpgrowth::{fp_growth_tree, FpTree};
+
+/// The frequent items, sorted from the most to the least frequent, which is the F-List.
+fn frequent_items<'a>(transactions: &[Vec<&'a str>], min_support: usize) -> Vec<(&'a str, usize)> {
+    let frequencies = transactions
+        .par_iter()
+        .fold(BTreeMap::new, |mut frequencies, transaction| {
+            for item in transaction {
+                *frequencies.entry(*item).or_insert(0) += 1;
+            }
+            frequencies
+        })
+        .reduce(BTreeMap::new, |mut frequencies, other| {
+            for (item, frequency) in other {
+                *frequencies.entry(item).or_insert(0) += frequency;
+            }
+            frequencies
+        });
+    let mut f_list = frequencies
+        .into_iter()
+        .filter(|(_, frequency)| *frequency >= min_support)
+        .collect::<Vec<_>>();
+    f_list.sort_by_key(|(item, frequency)| (Reverse(*frequency), *item));
+    f_list
+}
+
+/// Mines each group on its own thread, calling `mine_group` with the item sets of each group, and
+/// returns the F-List.
+///
+/// Items are assigned to groups in a round robin over the F-List. Each transaction, with its items
+/// sorted as in the F-List, is sent to the group of each of its items, cut after the last item of
+/// that group. The shard of a group then holds every path that an item set ending in one of its
+/// items can come from, so groups are mined independently, each with an FP-Tree of its shard.
+fn mine_groups<'a, F>(
+    transactions: &[Vec<&'a str>],
+    min_support: usize,
+    num_groups: usize,
+    mine_group: F,
+) -> Vec<(&'a str, usize)>
+where
+    F: Fn(Vec<(Vec<&'a str>, usize)>) + Sync,
+{
+    assert!(num_groups > 0, "num_groups must be greater than 0");
+    let f_list = frequent_items(transactions, min_support);
+    let ranks = f_list
+        .iter()
+        .enumerate()
+        .map(|(rank, (item, _))| (*item, rank))
+        .collect::<HashMap<_, _>>();
+    let group = |item: &str| ranks[item] % num_groups;
+
+    // Group dependent transactions.
+    let shards = transactions
+        .par_iter()
+        .fold(
+            || vec![vec![]; num_groups],
+            |mut shards: Vec<Vec<(Vec<&'a str>, usize)>>, transaction| {
+                let mut transaction = transaction
+                    .iter()
+                    .filter(|item| ranks.contains_key(*item))
+                    .copied()
+                    .collect::<Vec<_>>();
+                transaction.sort_by_key(|item| ranks[item]);
+                let mut sent = vec![false; num_groups];
+                for (index, item) in transaction.iter().enumerate().rev() {
+                    let group = group(item);
+                    if !sent[group] {
+                        sent[group] = true;
+                        shards[group].push((transaction[..=index].to_vec(), 1));
+                    }
+                }
+                shards
+            },
+        )
+        .reduce(
+            || vec![vec![]; num_groups],
+            |mut shards, other| {
+                for (shard, other) in shards.iter_mut().zip(other) {
+                    shard.extend(other);
+                }
+                shards
+            },
+        );
+
+    let frequencies = f_list.iter().copied().collect::<BTreeMap<_, _>>();
+    shards
+        .into_par_iter()
+        .enumerate()
+        .for_each(|(group_id, shard)| {
+            // The local tree keeps the global order of the items, so each item of the group only
+            // has item sets made of itself and items before it.
+            let fp_tree = FpTree {
+                root: FpTree::build_root(&shard, &frequencies, min_support),
+                frequencies: FpTree::count_frequencies(&shard),
+                min_support,
+            };
+            let mut item_sets = vec![];
+            for (item, frequency) in &f_list {
+                if group(item) != group_id {
+                    continue;
+                }
+                item_sets.push((vec![*item], *frequency));
+                let conditional_tree = fp_tree.build_conditional_tree(item);
+                fp_growth_tree(
+                    &conditional_tree,
+                    &mut |item_set, occurences| item_sets.push((item_set.to_vec(), occurences)),
+                    vec![*item],
+                );
+            }
+            mine_group(item_sets);
+        });
+    f_list
+}
+
+/// Collects frequent item sets from the provided transactions, using the Parallel FP-Growth (PFP)
+/// algorithm.
+///
+/// The frequent items are split into `num_groups` groups, and the transactions into a shard for
+/// each group, holding the prefixes of the transactions that can contain item sets ending in the
+/// items of the group. Groups are then mined in parallel, each with a small FP-Tree of its shard,
+/// instead of sharing one FP-Tree of all the transactions. This mirrors how PFP distributes the
+/// work across machines.
+///
+/// Item sets are reported as each group is mined, in no particular order.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+/// * `num_groups` - the number of groups the frequent items are split into.
+/// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+///   support is found.
+///
+/// # Panics
+/// Panics if `num_groups` is 0.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::pfp_growth;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// pfp_growth(transactions.as_slice(), 3, 2, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+pub fn pfp_growth<F>(
+    transactions: &[Vec<&str>],
+    min_support: usize,
+    num_groups: usize,
+    mut collect: F,
+) where
+    F: FnMut(&[&str], usize),
+{
+    std::thread::scope(|s| {
+        let (tx, rx) = channel();
+        s.spawn(move || {
+            mine_groups(transactions, min_support, num_groups, |item_sets| {
+                tx.send(item_sets).unwrap()
+            });
+        });
+        while let Ok(item_sets) = rx.recv() {
+            for (item_set, occurences) in item_sets {
+                collect(&item_set, occurences);
+            }
+        }
+    });
+}
+
+/// Orders item sets from the highest to the lowest support, and then by their items.
+fn compare_top<'a>(a: &(Vec<&'a str>, usize), b: &(Vec<&'a str>, usize)) -> Ordering {
+    b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0))
+}
+
+/// Collects, for each frequent item, the `k` frequent item sets containing it with the highest
+/// support, using the Parallel FP-Growth (PFP) algorithm.
+///
+/// Groups are mined as in [`pfp_growth`], and their item sets are aggregated by item, which is
+/// the last step of PFP. Item sets are reported with their items in lexicographic order, and ties
+/// in support are broken by those items, so the results are deterministic.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+/// * `num_groups` - the number of groups the frequent items are split into.
+/// * `k` - the number of item sets to collect for each item.
+/// * `collect` - a closure that will be invoked with each item and each of its top item sets,
+///   from the highest to the lowest support. Items come from the most to the least frequent.
+///
+/// # Panics
+/// Panics if `num_groups` is 0.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::pfp_growth_top_k;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// pfp_growth_top_k(transactions.as_slice(), 3, 2, 2, |item, item_set, occurences| {
+///     println!("{}: {:?}: {}", item, item_set, occurences)
+/// });
+/// ```
+pub fn pfp_growth_top_k<'a, F>(
+    transactions: &[Vec<&'a str>],
+    min_support: usize,
+    num_groups: usize,
+    k: usize,
+    mut collect: F,
+) where
+    F: FnMut(&str, &[&str], usize),
+{
+    type TopK<'a> = BTreeMap<&'a str, Vec<(Vec<&'a str>, usize)>>;
+    let keep_top_k = |item_sets: &mut Vec<(Vec<&'a str>, usize)>| {
+        item_sets.sort_by(compare_top);
+        item_sets.truncate(k);
+    };
+
+    let top_k = Mutex::new(TopK::new());
+    let f_list = mine_groups(transactions, min_support, num_groups, |item_sets| {
+        let mut group_top_k = TopK::new();
+        for (mut item_set, occurences) in item_sets {
+            item_set.sort();
+            for item in &item_set {
+                group_top_k
+                    .entry(*item)
+                    .or_default()
+                    .push((item_set.clone(), occurences));
+            }
+        }
+
+        for item_sets in group_top_k.values_mut() {
+            keep_top_k(item_sets);
+        }
+
+        let mut top_k = top_k.lock().unwrap();
+        for (item, mut item_sets) in group_top_k {
+            let top = top_k.entry(item).or_default();
+            top.append(&mut item_sets);
+            keep_top_k(top);
+        }
+    });
+
+    let top_k = top_k.into_inner().unwrap();
+    for (item, _) in f_list {
+        for (item_set, occurences) in &top_k[item] {
+            collect(item, item_set, *occurences);
+        }
+    }
+}
@@ -0,0 +1,317 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Constraints on the sequential patterns found by [`prefix_span_with_constraints`].
+///
+/// The gap between two consecutive elements of a pattern is the number of elements of the sequence
+/// that are skipped between them. Consecutive elements of a sequence have a gap of 0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceConstraints {
+    /// The minimum gap between consecutive elements of a pattern.
+    pub min_gap: usize,
+    /// The maximum gap between consecutive elements of a pattern, or `None` for no limit.
+    pub max_gap: Option<usize>,
+    /// The maximum number of items in a pattern, or `None` for no limit.
+    pub max_length: Option<usize>,
+}
+
+/// A sequence in the projected database, with the positions of the elements where the last element
+/// of the prefix was matched.
+#[derive(Debug)]
+struct Projection {
+    sequence: usize,
+    positions: Vec<usize>,
+}
+
+fn project<'a>(
+    extensions: BTreeMap<&'a str, BTreeSet<usize>>,
+    sequence: usize,
+    projected: &mut BTreeMap<&'a str, Vec<Projection>>,
+) {
+    for (item, positions) in extensions {
+        projected.entry(item).or_default().push(Projection {
+            sequence,
+            positions: positions.into_iter().collect(),
+        });
+    }
+}
+
+fn prefix_span_projected<'a, F>(
+    sequences: &[Vec<Vec<&'a str>>],
+    projected_database: &[Projection],
+    min_support: usize,
+    constraints: &SequenceConstraints,
+    collect: &mut F,
+    pattern: Vec<Vec<&'a str>>,
+) where
+    F: FnMut(&[Vec<&str>], usize),
+{
+    let length = pattern.iter().map(|element| element.len()).sum::<usize>();
+    if constraints
+        .max_length
+        .is_some_and(|max_length| length >= max_length)
+    {
+        return;
+    }
+
+    let last_item = pattern.last().and_then(|element| element.last()).copied();
+    let mut itemset_extensions = BTreeMap::new();
+    let mut sequence_extensions = BTreeMap::new();
+    for projection in projected_database {
+        let sequence = &sequences[projection.sequence];
+
+        // Items that can be added to the last element of the pattern, which are greater than its
+        // last item and found in the same element of the sequence.
+        if let Some(last_item) = last_item {
+            let mut extensions = BTreeMap::<_, BTreeSet<_>>::new();
+            for position in &projection.positions {
+                for item in sequence[*position].iter().filter(|i| **i > last_item) {
+                    extensions.entry(*item).or_default().insert(*position);
+                }
+            }
+            project(extensions, projection.sequence, &mut itemset_extensions);
+        }
+
+        // Items that can be appended to the pattern as a new element. Without a maximum gap, the
+        // elements reachable from the first position include the ones from every other position.
+        let positions = match constraints.max_gap {
+            Some(_) => projection.positions.as_slice(),
+            None => &projection.positions[..1],
+        };
+        let mut extensions = BTreeMap::<_, BTreeSet<_>>::new();
+        for position in positions {
+            let start = position + constraints.min_gap + 1;
+            let end = match constraints.max_gap {
+                Some(max_gap) => (position + max_gap + 2).min(sequence.len()),
+                None => sequence.len(),
+            };
+            for (next_position, element) in sequence.iter().enumerate().take(end).skip(start) {
+                for item in element {
+                    extensions.entry(*item).or_default().insert(next_position);
+                }
+            }
+        }
+        project(extensions, projection.sequence, &mut sequence_extensions);
+    }
+
+    for (item, projected_database) in itemset_extensions {
+        if projected_database.len() < min_support {
+            continue;
+        }
+        let mut pattern = pattern.clone();
+        pattern.last_mut().unwrap().push(item);
+        collect(pattern.as_slice(), projected_database.len());
+        prefix_span_projected(
+            sequences,
+            &projected_database,
+            min_support,
+            constraints,
+            collect,
+            pattern,
+        );
+    }
+
+    for (item, projected_database) in sequence_extensions {
+        if projected_database.len() < min_support {
+            continue;
+        }
+        let mut pattern = pattern.clone();
+        pattern.push(vec![item]);
+        collect(pattern.as_slice(), projected_database.len());
+        prefix_span_projected(
+            sequences,
+            &projected_database,
+            min_support,
+            constraints,
+            collect,
+            pattern,
+        );
+    }
+}
+
+/// Collects frequent sequential patterns from the provided sequences, using the PrefixSpan
+/// algorithm.
+///
+/// A sequence is an ordered list of elements, where each element is an unordered set of items.
+/// A pattern is supported by a sequence when each of its elements is a subset of a distinct element
+/// of the sequence, in the same order. The items of each element of a pattern are reported in
+/// lexicographic order.
+///
+/// # Arguments
+/// * `sequences` - a list of sequences.
+/// * `min_support` - the minimum support.
+/// * `collect` - a closure that will be invoked when a new pattern that matches the minimum
+///   support is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::prefix_span;
+///
+/// let sequences = vec![
+///     vec![vec!["home"], vec!["search"], vec!["product", "reviews"], vec!["cart"]],
+///     vec![vec!["home"], vec!["product"], vec!["cart"]],
+///     vec![vec!["search"], vec!["product", "reviews"]],
+/// ];
+///
+/// prefix_span(sequences.as_slice(), 2, |pattern, occurences| {
+///     println!("{:?}: {}", pattern, occurences)
+/// });
+/// ```
+pub fn prefix_span<F>(sequences: &[Vec<Vec<&str>>], min_support: usize, collect: F)
+where
+    F: FnMut(&[Vec<&str>], usize),
+{
+    prefix_span_with_constraints(sequences, min_support, Default::default(), collect);
+}
+
+/// Collects frequent sequential patterns from the provided sequences, using the PrefixSpan
+/// algorithm, only reporting the patterns that satisfy the provided constraints.
+///
+/// A sequence supports a pattern only if the pattern can be found in it with every gap between
+/// consecutive elements within `constraints`.
+///
+/// # Arguments
+/// * `sequences` - a list of sequences.
+/// * `min_support` - the minimum support.
+/// * `constraints` - gap and length constraints on the patterns.
+/// * `collect` - a closure that will be invoked when a new pattern that matches the minimum
+///   support is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::{prefix_span_with_constraints, SequenceConstraints};
+///
+/// let sequences = vec![
+///     vec![vec!["home"], vec!["search"], vec!["product", "reviews"], vec!["cart"]],
+///     vec![vec!["home"], vec!["product"], vec!["cart"]],
+///     vec![vec!["search"], vec!["product", "reviews"]],
+/// ];
+/// let constraints = SequenceConstraints {
+///     max_gap: Some(0),
+///     max_length: Some(2),
+///     ..Default::default()
+/// };
+///
+/// prefix_span_with_constraints(sequences.as_slice(), 2, constraints, |pattern, occurences| {
+///     println!("{:?}: {}", pattern, occurences)
+/// });
+/// ```
+pub fn prefix_span_with_constraints<F>(
+    sequences: &[Vec<Vec<&str>>],
+    min_support: usize,
+    constraints: SequenceConstraints,
+    mut collect: F,
+) where
+    F: FnMut(&[Vec<&str>], usize),
+{
+    // Items within an element are unordered, so they are kept sorted to only generate each
+    // pattern once.
+    let sequences = sequences
+        .iter()
+        .map(|sequence| {
+            sequence
+                .iter()
+                .map(|element| {
+                    let mut element = element.clone();
+                    element.sort();
+                    element.dedup();
+                    element
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    if constraints.max_length == Some(0) {
+        return;
+    }
+
+    // Project the database on each item.
+    let mut projected_databases = BTreeMap::new();
+    for (index, sequence) in sequences.iter().enumerate() {
+        let mut positions = BTreeMap::<_, BTreeSet<_>>::new();
+        for (position, element) in sequence.iter().enumerate() {
+            for item in element {
+                positions.entry(*item).or_default().insert(position);
+            }
+        }
+        project(positions, index, &mut projected_databases);
+    }
+
+    for (item, projected_database) in projected_databases {
+        if projected_database.len() < min_support {
+            continue;
+        }
+        let pattern = vec![vec![item]];
+        collect(pattern.as_slice(), projected_database.len());
+        prefix_span_projected(
+            &sequences,
+            &projected_database,
+            min_support,
+            &constraints,
+            &mut collect,
+            pattern,
+        );
+    }
+}
+
+/// A rule predicting the next element of a sequence from the elements that preceded it.
+#[derive(Debug, PartialEq)]
+pub struct SequenceRule {
+    pub antecedent: Vec<Vec<String>>,
+    pub consequent: Vec<String>,
+    pub antecedent_support: f32,
+    pub consequent_support: f32,
+    pub combined_support: f32,
+    pub lift: f32,
+    pub confidence: f32,
+}
+
+/// Generates sequence rules from frequent sequential patterns, where the consequent is the last
+/// element of a pattern, and the antecedent are the elements before it.
+///
+/// The items of each element in `frequent_patterns` are expected to be sorted, as reported by
+/// [`prefix_span`].
+pub fn generate_sequence_rules<F>(
+    frequent_patterns: &HashMap<Vec<Vec<String>>, usize>,
+    num_sequences: usize,
+    on_sequence_rule: &mut F,
+) where
+    F: FnMut(SequenceRule),
+{
+    for (pattern, occurences) in frequent_patterns {
+        if pattern.len() == 1 {
+            continue;
+        }
+        let (consequent, antecedent) = pattern.split_last().unwrap();
+        let antecedent = antecedent.to_vec();
+        let combined_support = *occurences as f32 / num_sequences as f32;
+        let antecedent_support =
+            *frequent_patterns.get(&antecedent).unwrap() as f32 / num_sequences as f32;
+        let consequent_support = *frequent_patterns.get(&vec![consequent.clone()]).unwrap() as f32
+            / num_sequences as f32;
+
+        let confidence = combined_support / antecedent_support;
+        on_sequence_rule(SequenceRule {
+            antecedent,
+            consequent: consequent.clone(),
+            antecedent_support,
+            consequent_support,
+            combined_support,
+            lift: confidence / consequent_support,
+            confidence,
+        });
+    }
+}
@@ -0,0 +1,182 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{BTreeMap, HashSet};
+
+use crate::fpgrowth::{fp_growth_tree, FpTree};
+
+/// Collects rare item sets from the provided transactions, which are the item sets with a support
+/// of at least `min_support`, but below `max_support`.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support, used to ignore item sets which are too uncommon.
+/// * `max_support` - the support from which item sets are considered frequent.
+/// * `collect` - a closure that will be invoked when a new rare item set is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::rare_item_sets;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// rare_item_sets(transactions.as_slice(), 1, 3, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+pub fn rare_item_sets<F>(
+    transactions: &[Vec<&str>],
+    min_support: usize,
+    max_support: usize,
+    mut collect: F,
+) where
+    F: FnMut(&[&str], usize),
+{
+    let fp_tree = FpTree::new(transactions, min_support);
+    fp_growth_tree(
+        &fp_tree,
+        &mut |item_set, occurences| {
+            if occurences < max_support {
+                collect(item_set, occurences);
+            }
+        },
+        vec![],
+    );
+}
+
+/// Collects minimal rare item sets from the provided transactions, which are the rare item sets
+/// where every proper subset is frequent.
+///
+/// Every rare item set is a superset of a minimal rare item set, so those are a compact
+/// representation of the rare item sets. Candidates are generated from the frequent item sets, in
+/// the same way as Apriori does, and their support is calculated with a scan of the transactions.
+/// Item sets are reported with their items in lexicographic order.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support, used to ignore item sets which are too uncommon. A
+///   minimum support of 1 reports all minimal rare item sets present in the transactions.
+/// * `max_support` - the support from which item sets are considered frequent.
+/// * `collect` - a closure that will be invoked when a new minimal rare item set is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::minimal_rare_item_sets;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+///
+/// minimal_rare_item_sets(transactions.as_slice(), 1, 3, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+/// ```
+pub fn minimal_rare_item_sets<F>(
+    transactions: &[Vec<&str>],
+    min_support: usize,
+    max_support: usize,
+    mut collect: F,
+) where
+    F: FnMut(&[&str], usize),
+{
+    let sets = transactions
+        .iter()
+        .map(|t| t.iter().copied().collect::<HashSet<_>>())
+        .collect::<Vec<_>>();
+    let count = |item_set: &[&str]| {
+        sets.iter()
+            .filter(|t| item_set.iter().all(|i| t.contains(i)))
+            .count()
+    };
+
+    // Single items are minimal rare item sets when they are not frequent.
+    let mut frequencies = BTreeMap::new();
+    for set in &sets {
+        for item in set {
+            *frequencies.entry(*item).or_insert(0) += 1;
+        }
+    }
+    for (item, frequency) in &frequencies {
+        if *frequency >= min_support && *frequency < max_support {
+            collect(&[item], *frequency);
+        }
+    }
+
+    // Frequent item sets, grouped by size.
+    let mut frequent_item_sets = vec![];
+    let fp_tree = FpTree::new(transactions, max_support);
+    fp_growth_tree(
+        &fp_tree,
+        &mut |item_set, _| {
+            let mut item_set = item_set.to_vec();
+            item_set.sort();
+            if frequent_item_sets.len() < item_set.len() {
+                frequent_item_sets.resize_with(item_set.len(), HashSet::new);
+            }
+            frequent_item_sets[item_set.len() - 1].insert(item_set);
+        },
+        vec![],
+    );
+
+    // Join frequent item sets of the same size sharing all items but the last. A candidate where
+    // every subset is frequent, but which is not frequent itself, is a minimal rare item set.
+    for index in 0..frequent_item_sets.len() {
+        let mut item_sets = frequent_item_sets[index].iter().collect::<Vec<_>>();
+        item_sets.sort();
+        for (i, a) in item_sets.iter().enumerate() {
+            for b in &item_sets[i + 1..] {
+                if a[..index] != b[..index] {
+                    break;
+                }
+                let mut candidate = a.to_vec();
+                candidate.push(b[index]);
+                let is_frequent = frequent_item_sets
+                    .get(index + 1)
+                    .is_some_and(|s| s.contains(&candidate));
+                let subsets_are_frequent = (0..candidate.len()).all(|skip| {
+                    let subset = candidate
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != skip)
+                        .map(|(_, item)| *item)
+                        .collect::<Vec<_>>();
+                    frequent_item_sets[index].contains(&subset)
+                });
+                if is_frequent || !subsets_are_frequent {
+                    continue;
+                }
+                let occurences = count(&candidate);
+                if occurences >= min_support {
+                    collect(candidate.as_slice(), occurences);
+                }
+            }
+        }
+    }
+}
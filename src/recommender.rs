@@ -0,0 +1,195 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::AssociationRule;
+
+/// How the rules recommending the same item are combined into its score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    /// The highest confidence among the rules.
+    MaxConfidence,
+    /// The highest lift among the rules.
+    MaxLift,
+    /// The sum of the confidences of the rules.
+    SumConfidence,
+    /// The sum of the lifts of the rules.
+    SumLift,
+}
+
+impl Aggregation {
+    fn aggregate(&self, score: f32, rule: &AssociationRule) -> f32 {
+        match self {
+            Aggregation::MaxConfidence => score.max(rule.confidence),
+            Aggregation::MaxLift => score.max(rule.lift),
+            Aggregation::SumConfidence => score + rule.confidence,
+            Aggregation::SumLift => score + rule.lift,
+        }
+    }
+}
+
+/// An item recommended for a basket, with its score and the rules that recommend it.
+#[derive(Debug, PartialEq)]
+pub struct Recommendation<'a> {
+    pub item: String,
+    pub score: f32,
+    /// The rules which antecedent is in the basket and which consequent contains the item, from
+    /// the most to the least confident.
+    pub rules: Vec<&'a AssociationRule>,
+}
+
+/// A trie of antecedents, with items in lexicographic order. Each node holds the rules which
+/// antecedent is the path to it.
+#[derive(Debug, Default)]
+struct RuleNode {
+    children: BTreeMap<String, RuleNode>,
+    rules: Vec<usize>,
+}
+
+impl RuleNode {
+    fn insert(&mut self, antecedent: &[String], rule: usize) {
+        match antecedent.split_first() {
+            None => self.rules.push(rule),
+            Some((item, rest)) => self
+                .children
+                .entry(item.clone())
+                .or_default()
+                .insert(rest, rule),
+        }
+    }
+
+    /// Collects the rules which antecedent is a subset of the basket, which must be sorted.
+    fn find_subsets(&self, basket: &[&str], into: &mut Vec<usize>) {
+        into.extend(&self.rules);
+        for (index, item) in basket.iter().enumerate() {
+            if let Some(child) = self.children.get(*item) {
+                child.find_subsets(&basket[index + 1..], into);
+            }
+        }
+    }
+}
+
+/// Recommends items for a basket from association rules.
+///
+/// Rules are indexed by their antecedents, so only the rules which antecedent is contained in the
+/// basket are visited. Each item in the consequent of those rules which is not already in the
+/// basket is recommended, with a score that aggregates the rules recommending it. Negative rules
+/// are ignored.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::{Aggregation, AssociationRule, Recommender};
+///
+/// let rules = vec![
+///     AssociationRule::new(
+///         vec!["beer".to_string()],
+///         vec!["potato chips".to_string()],
+///         0.5,
+///         0.5,
+///         0.45,
+///         1.8,
+///         0.9,
+///     ),
+/// ];
+///
+/// let recommender = Recommender::new(rules);
+/// for recommendation in recommender.recommend(&["beer"], 3, Aggregation::MaxConfidence) {
+///     println!("{}: {}", recommendation.item, recommendation.score);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Recommender {
+    rules: Vec<AssociationRule>,
+    root: RuleNode,
+}
+
+impl Recommender {
+    /// Creates a recommender from association rules.
+    pub fn new(rules: Vec<AssociationRule>) -> Self {
+        let rules = rules
+            .into_iter()
+            .filter(|rule| !rule.is_negative())
+            .collect::<Vec<_>>();
+        let mut root = RuleNode::default();
+        for (index, rule) in rules.iter().enumerate() {
+            let mut antecedent = rule.antecedent_set.clone();
+            antecedent.sort();
+            antecedent.dedup();
+            root.insert(&antecedent, index);
+        }
+        Self { rules, root }
+    }
+
+    /// The rules used for recommendations.
+    pub fn rules(&self) -> &[AssociationRule] {
+        &self.rules
+    }
+
+    /// Recommends up to `n` items for a basket, from the highest to the lowest score.
+    ///
+    /// # Arguments
+    /// * `basket` - the items already in the basket, which are never recommended.
+    /// * `n` - the maximum number of recommendations.
+    /// * `aggregation` - how the rules recommending an item are combined into its score.
+    pub fn recommend(
+        &self,
+        basket: &[&str],
+        n: usize,
+        aggregation: Aggregation,
+    ) -> Vec<Recommendation<'_>> {
+        let mut basket = basket.to_vec();
+        basket.sort();
+        basket.dedup();
+        let mut matches = vec![];
+        self.root.find_subsets(&basket, &mut matches);
+
+        let mut recommendations: HashMap<&str, Recommendation> = HashMap::new();
+        for rule in matches.into_iter().map(|index| &self.rules[index]) {
+            for item in &rule.consequent_set {
+                if basket.binary_search(&item.as_str()).is_ok() {
+                    continue;
+                }
+                let recommendation =
+                    recommendations
+                        .entry(item)
+                        .or_insert_with(|| Recommendation {
+                            item: item.clone(),
+                            score: 0.0,
+                            rules: vec![],
+                        });
+                recommendation.score = aggregation.aggregate(recommendation.score, rule);
+                recommendation.rules.push(rule);
+            }
+        }
+
+        let mut recommendations = recommendations.into_values().collect::<Vec<_>>();
+        recommendations.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.item.cmp(&b.item))
+        });
+        recommendations.truncate(n);
+        for recommendation in &mut recommendations {
+            recommendation.rules.sort_by(|a, b| {
+                b.confidence
+                    .partial_cmp(&a.confidence)
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+        recommendations
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::VecDeque;
+
+use crate::CanTree;
+
+/// Mines frequent item sets over the most recent transactions of a stream.
+///
+/// The window holds at most `capacity` transactions, and transactions can also be expired by age,
+/// using the timestamps they were inserted with. Expired transactions are removed from the
+/// underlying [`CanTree`], so mining the window never requires rebuilding the tree.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::SlidingWindow;
+///
+/// let mut window = SlidingWindow::new(3);
+/// window.insert_transactions(0, &[vec!["A", "B"], vec!["A", "C"]]);
+/// window.insert_transactions(1, &[vec!["A", "B"], vec!["B", "C"]]);
+///
+/// // The window keeps the last 3 transactions.
+/// window.mine(2, |item_set, occurences| {
+///     println!("{:?}: {}", item_set, occurences)
+/// });
+///
+/// // Drop the transactions inserted before the timestamp 1.
+/// window.expire_before(1);
+/// assert_eq!(window.len(), 2);
+/// ```
+#[derive(Debug)]
+pub struct SlidingWindow<'a> {
+    can_tree: CanTree<'a>,
+    window: VecDeque<(u64, Vec<&'a str>)>,
+    capacity: usize,
+}
+
+impl<'a> SlidingWindow<'a> {
+    /// Creates a window holding at most `capacity` transactions. Use `usize::MAX` to only expire
+    /// transactions with [`SlidingWindow::expire_before`].
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            can_tree: CanTree::new(),
+            window: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// The number of transactions in the window.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.window.is_empty()
+    }
+
+    /// Inserts a batch of transactions in the window, expiring the oldest transactions when the
+    /// window is over capacity. Timestamps are expected to be non-decreasing.
+    pub fn insert_transactions(&mut self, timestamp: u64, transactions: &[Vec<&'a str>]) {
+        self.can_tree.insert_transactions(transactions);
+        self.window
+            .extend(transactions.iter().map(|t| (timestamp, t.clone())));
+
+        let overflow = self.window.len().saturating_sub(self.capacity);
+        self.expire(overflow);
+    }
+
+    /// Expires every transaction inserted before `timestamp`.
+    pub fn expire_before(&mut self, timestamp: u64) {
+        let expired = self
+            .window
+            .iter()
+            .take_while(|(inserted_at, _)| *inserted_at < timestamp)
+            .count();
+        self.expire(expired);
+    }
+
+    fn expire(&mut self, count: usize) {
+        let expired = self
+            .window
+            .drain(..count)
+            .map(|(_, transaction)| transaction)
+            .collect::<Vec<_>>();
+        self.can_tree.remove_transactions(expired.as_slice());
+    }
+
+    /// Collects frequent item sets from the transactions currently in the window.
+    ///
+    /// # Arguments
+    /// * `min_support` - the minimum support.
+    /// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+    ///   support is found.
+    pub fn mine<F>(&self, min_support: usize, collect: F)
+    where
+        F: FnMut(&[&str], usize),
+    {
+        self.can_tree.mine(min_support, collect);
+    }
+}
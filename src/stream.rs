@@ -0,0 +1,121 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::thread::{self, JoinHandle};
+
+use futures::channel::mpsc::{channel, Receiver};
+use futures::executor::block_on;
+use futures::{SinkExt, Stream, StreamExt};
+
+use crate::fp_growth_iter;
+
+/// A stream of frequent item sets and their occurences, created by [`fp_growth_stream`].
+///
+/// Item sets are mined on their own thread, which stops when the stream is dropped.
+#[derive(Debug)]
+pub struct FrequentItemSetStream {
+    receiver: Receiver<(Vec<String>, usize)>,
+    miner: JoinHandle<usize>,
+}
+
+impl FrequentItemSetStream {
+    /// Stops mining, and waits for the mining thread to finish. Returns the number of item sets
+    /// that were sent to the stream, including the ones that were never polled.
+    pub fn cancel(self) -> usize {
+        let FrequentItemSetStream {
+            mut receiver,
+            miner,
+        } = self;
+        receiver.close();
+        miner.join().unwrap()
+    }
+}
+
+impl Stream for FrequentItemSetStream {
+    type Item = (Vec<String>, usize);
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_next_unpin(cx)
+    }
+}
+
+/// Returns a stream of the frequent item sets of the provided transactions, with their
+/// occurences, so they can be mined from async code without blocking the executor.
+///
+/// Item sets are mined by [`fp_growth_iter`] on a thread of their own, in the same order, and
+/// mining waits while `buffer` item sets are waiting to be polled. Dropping the stream stops
+/// mining at the next item set.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions.
+/// * `min_support` - the minimum support.
+/// * `buffer` - the number of item sets that can be mined ahead of the stream being polled, which
+///   is at least one.
+///
+/// # Example
+/// ```
+/// use futures::executor::block_on;
+/// use futures::StreamExt;
+///
+/// use fpgrowth_rs::fp_growth_stream;
+///
+/// let transactions = vec![
+///     vec!["E", "A", "D", "B"],
+///     vec!["D", "A", "C", "E", "B"],
+///     vec!["C", "A", "B", "E"],
+///     vec!["B", "A", "D"],
+///     vec!["D"],
+///     vec!["D", "B"],
+///     vec!["A", "D", "E"],
+///     vec!["B", "C"],
+/// ];
+/// let transactions = transactions
+///     .iter()
+///     .map(|t| t.iter().map(|i| i.to_string()).collect())
+///     .collect();
+///
+/// block_on(async {
+///     let mut item_sets = fp_growth_stream(transactions, 3, 16);
+///     while let Some((item_set, occurences)) = item_sets.next().await {
+///         println!("{:?}: {}", item_set, occurences)
+///     }
+/// });
+/// ```
+pub fn fp_growth_stream(
+    transactions: Vec<Vec<String>>,
+    min_support: usize,
+    buffer: usize,
+) -> FrequentItemSetStream {
+    // The channel holds one more item set than its buffer for each sender.
+    let (mut sender, receiver) = channel(buffer.saturating_sub(1));
+    let miner = thread::spawn(move || {
+        let transactions = transactions
+            .iter()
+            .map(|t| t.iter().map(String::as_str).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut sent = 0;
+        for (item_set, occurences) in fp_growth_iter(transactions.as_slice(), min_support) {
+            let item_set = item_set.iter().map(|i| i.to_string()).collect();
+            // Sending fails once the stream has been dropped.
+            if block_on(sender.send((item_set, occurences))).is_err() {
+                break;
+            }
+            sent += 1;
+        }
+        sent
+    });
+    FrequentItemSetStream { receiver, miner }
+}
@@ -0,0 +1,86 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::fpgrowth::{fp_growth_tree, FpTree, NodeKey};
+
+/// The key of a node in an UF-Tree. Unlike in an FP-Tree, transactions only share a node when the
+/// item also has the same existential probability in both. Probabilities are stored as bits, and
+/// as they are never negative, the ordering of the bits matches the ordering of the probabilities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct UfKey<'a> {
+    item: &'a str,
+    probability: u64,
+}
+
+impl<'a> NodeKey<'a, f64> for UfKey<'a> {
+    fn item(&self) -> &'a str {
+        self.item
+    }
+
+    // The item only exists with its probability in the transactions going through the node, which
+    // makes expected supports in the conditional trees the ones of the extended item sets.
+    fn support(&self, count: f64) -> f64 {
+        count * f64::from_bits(self.probability)
+    }
+}
+
+/// Collects frequent item sets from uncertain transactions, using the UF-Growth algorithm.
+///
+/// Each item in a transaction is paired with the probability of it existing in the transaction,
+/// and items are assumed to be independent from each other. The expected support of an item set is
+/// the sum, over all transactions, of the product of the probabilities of its items.
+///
+/// # Arguments
+/// * `transactions` - a list of transactions, where each item is paired with its existential
+///   probability. An item should appear at most once in each transaction.
+/// * `min_expected_support` - the minimum expected support.
+/// * `collect` - a closure that will be invoked when a new item set that matches the minimum
+///   expected support is found.
+///
+/// # Example
+/// ```
+/// use fpgrowth_rs::uf_growth;
+///
+/// let transactions = vec![
+///     vec![("A", 0.9), ("B", 0.5)],
+///     vec![("A", 0.8), ("C", 0.7)],
+///     vec![("A", 0.9), ("B", 0.6), ("C", 0.2)],
+/// ];
+///
+/// uf_growth(transactions.as_slice(), 1.0, |item_set, expected_support| {
+///     println!("{:?}: {}", item_set, expected_support)
+/// });
+/// ```
+pub fn uf_growth<F>(transactions: &[Vec<(&str, f64)>], min_expected_support: f64, mut collect: F)
+where
+    F: FnMut(&[&str], f64),
+{
+    // Items that certainly don't exist are left out of the tree.
+    let transactions = transactions
+        .iter()
+        .map(|transaction| {
+            let keys = transaction
+                .iter()
+                .filter(|(_, probability)| *probability > 0.0)
+                .map(|(item, probability)| UfKey {
+                    item,
+                    probability: probability.to_bits(),
+                })
+                .collect::<Vec<_>>();
+            (keys, 1.0)
+        })
+        .collect::<Vec<_>>();
+    let uf_tree = FpTree::build_fp_tree(transactions.as_slice(), min_expected_support);
+    fp_growth_tree(&uf_tree, &mut collect, vec![]);
+}
@@ -0,0 +1,98 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use fpgrowth_rs::{apriori, Apriori, FpGrowth, FrequentItemsetMiner};
+
+/// Dense transactions over 8 items, so there are enough candidates to split the leaves of the
+/// hash tree.
+fn transactions() -> Vec<Vec<&'static str>> {
+    vec![
+        vec!["A", "B", "C", "D", "E", "F", "G", "H"],
+        vec!["A", "B", "C", "D", "E", "F"],
+        vec!["C", "D", "E", "F", "G", "H"],
+        vec!["A", "B", "E", "F", "G", "H"],
+        vec!["A", "C", "E", "G"],
+        vec!["B", "D", "F", "H"],
+        vec!["A", "B", "C", "D"],
+        vec!["E", "F", "G", "H"],
+        vec!["A", "D", "E", "H"],
+        vec!["B", "C", "F", "G"],
+    ]
+}
+
+#[test]
+fn test_apriori() {
+    let transactions = vec![
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+    ];
+
+    let mut results = vec![];
+    apriori(transactions.as_slice(), 3, |item_set, occurences| {
+        let item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        results.push((item_set.join(","), occurences));
+    });
+
+    // Item sets are reported level by level, with their items in lexicographic order.
+    let expected = [
+        ("A", 5),
+        ("B", 6),
+        ("C", 3),
+        ("D", 6),
+        ("E", 4),
+        ("A,B", 4),
+        ("A,D", 4),
+        ("A,E", 4),
+        ("B,C", 3),
+        ("B,D", 4),
+        ("B,E", 3),
+        ("D,E", 3),
+        ("A,B,D", 3),
+        ("A,B,E", 3),
+        ("A,D,E", 3),
+    ]
+    .map(|(item_set, occurences)| (item_set.to_string(), occurences));
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_apriori_matches_fp_growth() {
+    let transactions = transactions();
+    for min_support in [1, 2, 3, 5] {
+        let expected = common::mine(&FpGrowth, &transactions, min_support);
+        assert!(!expected.is_empty());
+        assert_eq!(common::mine(&Apriori, &transactions, min_support), expected);
+    }
+}
+
+#[test]
+fn test_zero_min_support() {
+    // Item sets that never occur are not frequent, as with a minimum support of one.
+    let sparse = vec![vec!["A", "B"], vec!["A"], vec!["C"]];
+    for transactions in [sparse, transactions()] {
+        let expected = common::mine(&FpGrowth, &transactions, 1);
+        let miners: [&dyn FrequentItemsetMiner; 2] = [&FpGrowth, &Apriori];
+        for miner in miners {
+            assert_eq!(common::mine(miner, &transactions, 0), expected);
+        }
+    }
+}
@@ -1,6 +1,13 @@
+mod common;
+
 use std::collections::HashMap;
 
-use fpgrowth_rs::{fp_growth, generate_association_rules, AssociationRule};
+#[cfg(feature = "parallel")]
+use fpgrowth_rs::par_generate_association_rules;
+use fpgrowth_rs::{
+    fp_growth, generate_association_rules, generate_confident_association_rules,
+    generate_negative_association_rules, AssociationRule,
+};
 
 #[test]
 fn test_association_rules() {
@@ -60,3 +67,161 @@ fn test_association_rules() {
         )
     );
 }
+
+#[test]
+fn test_negative_association_rules() {
+    let mut transactions = vec![];
+    transactions.extend(vec![vec!["organic", "bread"]; 4]);
+    transactions.push(vec!["organic", "cigarettes"]);
+    transactions.extend(vec![vec!["cigarettes", "beer"]; 3]);
+    transactions.extend(vec![vec!["bread"]; 2]);
+
+    let num_transactions = transactions.len();
+    let mut results = HashMap::new();
+    fp_growth(transactions.as_slice(), 1, |item_set, occurences| {
+        let mut items = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        items.sort();
+        results.insert(items, occurences);
+    });
+
+    let mut association_rules = vec![];
+    generate_negative_association_rules(
+        &results,
+        num_transactions,
+        0.7,
+        0.05,
+        &mut |association_rule| {
+            association_rules.push(association_rule);
+        },
+    );
+
+    // organic -> ¬cigarettes, cigarettes -> ¬organic, ¬bread -> ¬organic, ¬cigarettes -> ¬beer
+    // and ¬beer -> ¬cigarettes.
+    assert_eq!(association_rules.len(), 5);
+    assert!(association_rules.iter().all(|r| r.is_negative()));
+
+    let organic = association_rules
+        .iter()
+        .find(|r| r.antecedent_set == vec!["organic".to_string()])
+        .unwrap();
+    assert_eq!(organic.consequent_set, vec!["cigarettes".to_string()]);
+    assert!(!organic.antecedent_negated);
+    assert!(organic.consequent_negated);
+    assert!((organic.antecedent_support - 0.5).abs() < 1e-6);
+    assert!((organic.consequent_support - 0.6).abs() < 1e-6);
+    assert!((organic.combined_support - 0.4).abs() < 1e-6);
+    assert!((organic.confidence - 0.8).abs() < 1e-6);
+    assert!((organic.lift - 0.8 / 0.6).abs() < 1e-6);
+
+    let beer = association_rules
+        .iter()
+        .find(|r| r.antecedent_set == vec!["beer".to_string()])
+        .unwrap();
+    assert!(beer.antecedent_negated && beer.consequent_negated);
+    assert!((beer.combined_support - 0.6).abs() < 1e-6);
+    assert!((beer.confidence - 0.6 / 0.7).abs() < 1e-6);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_par_association_rules() {
+    let transactions = vec![
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+        vec!["F", "A", "C"],
+        vec!["F", "E", "D"],
+    ];
+
+    let mut results = HashMap::new();
+    fp_growth(
+        transactions.as_slice(),
+        2,
+        common::collect_into(&mut results),
+    );
+
+    let mut expected = vec![];
+    generate_association_rules(&results, transactions.len(), &mut |rule| {
+        expected.push(rule)
+    });
+
+    let mut deterministic = vec![];
+    par_generate_association_rules(&results, transactions.len(), true, &mut |rule| {
+        deterministic.push(rule)
+    });
+    let mut unordered = vec![];
+    par_generate_association_rules(&results, transactions.len(), false, &mut |rule| {
+        unordered.push(rule)
+    });
+
+    assert!(!expected.is_empty());
+    let key = |rule: &AssociationRule| {
+        let mut set = rule.antecedent_set.clone();
+        set.extend(rule.consequent_set.clone());
+        set.sort();
+        (set, rule.consequent_set.clone())
+    };
+    expected.sort_by_key(key);
+    assert_eq!(deterministic.len(), expected.len());
+    assert!(deterministic
+        .windows(2)
+        .all(|w| key(&w[0]).0 <= key(&w[1]).0));
+    unordered.sort_by_key(key);
+    assert_eq!(unordered, expected);
+    deterministic.sort_by_key(key);
+    assert_eq!(deterministic, expected);
+}
+
+#[test]
+fn test_multi_item_consequents() {
+    let transactions = vec![
+        vec!["A", "B", "C", "D", "E"],
+        vec!["A", "B", "C", "D"],
+        vec!["A", "B", "C", "E"],
+        vec!["A", "B", "D"],
+        vec!["A", "C", "E"],
+        vec!["B", "C", "D", "E"],
+        vec!["B", "D"],
+        vec!["C", "E"],
+    ];
+
+    let mut results = HashMap::new();
+    fp_growth(
+        transactions.as_slice(),
+        2,
+        common::collect_into(&mut results),
+    );
+
+    // Every split of an item set in two non-empty sets is a rule.
+    let mut all_rules = vec![];
+    generate_association_rules(&results, transactions.len(), &mut |rule| {
+        all_rules.push(rule)
+    });
+    let expected_rules = results
+        .keys()
+        .map(|item_set| (1 << item_set.len()) - 2)
+        .sum::<usize>();
+    assert_eq!(all_rules.len(), expected_rules);
+    assert!(all_rules.iter().any(|rule| rule.consequent_set.len() == 3));
+
+    // Pruned consequents don't miss any rule with enough confidence.
+    let key = |rule: &AssociationRule| (rule.antecedent_set.clone(), rule.consequent_set.clone());
+    let mut expected = all_rules
+        .iter()
+        .filter(|rule| rule.confidence >= 0.5)
+        .map(key)
+        .collect::<Vec<_>>();
+    let mut confident = vec![];
+    generate_confident_association_rules(&results, transactions.len(), 0.5, &mut |rule| {
+        confident.push(key(&rule))
+    });
+    expected.sort();
+    confident.sort();
+    assert!(confident.len() < all_rules.len());
+    assert_eq!(confident, expected);
+}
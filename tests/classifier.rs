@@ -0,0 +1,98 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::{fp_growth, generate_class_association_rules, CbaClassifier};
+
+fn transactions() -> Vec<Vec<&'static str>> {
+    let mut transactions = vec![];
+    transactions.extend(vec![vec!["monthly", "support calls", "churn"]; 4]);
+    transactions.extend(vec![vec!["monthly", "support calls", "stay"]; 1]);
+    transactions.extend(vec![vec!["monthly", "stay"]; 3]);
+    transactions.extend(vec![vec!["monthly", "churn"]; 1]);
+    transactions.extend(vec![vec!["yearly", "support calls", "stay"]; 3]);
+    transactions.extend(vec![vec!["yearly", "stay"]; 4]);
+    transactions
+}
+
+#[test]
+fn test_class_association_rules() {
+    let transactions = transactions();
+    let mut frequent_item_sets = HashMap::new();
+    fp_growth(transactions.as_slice(), 2, |item_set, occurences| {
+        let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        item_set.sort();
+        frequent_item_sets.insert(item_set, occurences);
+    });
+
+    let mut rules = vec![];
+    generate_class_association_rules(
+        &frequent_item_sets,
+        transactions.len(),
+        &["churn", "stay"],
+        &mut |rule| rules.push(rule),
+    );
+
+    assert!(!rules.is_empty());
+    for rule in &rules {
+        assert_eq!(rule.consequent_set.len(), 1);
+        assert!(["churn", "stay"].contains(&rule.consequent_set[0].as_str()));
+        assert!(!rule
+            .antecedent_set
+            .iter()
+            .any(|i| i == "churn" || i == "stay"));
+    }
+    let rule = rules
+        .iter()
+        .find(|r| {
+            r.antecedent_set == vec!["monthly".to_string(), "support calls".to_string()]
+                && r.consequent_set == vec!["churn".to_string()]
+        })
+        .unwrap();
+    assert!((rule.confidence - 0.8).abs() < 1e-6);
+}
+
+#[test]
+fn test_cba_classifier() {
+    let transactions = transactions();
+    let classifier = CbaClassifier::train(transactions.as_slice(), &["churn", "stay"], 2, 0.5);
+
+    // Customers on yearly plans always stay, and the ones on monthly plans calling support
+    // usually churn.
+    let rules = classifier
+        .rules()
+        .iter()
+        .map(|r| (r.antecedent_set.join(","), r.consequent_set.join(",")))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        rules,
+        vec![
+            ("yearly".to_string(), "stay".to_string()),
+            ("monthly,support calls".to_string(), "churn".to_string()),
+        ]
+    );
+    assert_eq!(classifier.default_class(), Some("stay"));
+
+    assert_eq!(
+        classifier.predict(&["monthly", "support calls"]),
+        Some("churn")
+    );
+    assert_eq!(classifier.predict(&["monthly"]), Some("stay"));
+    assert_eq!(
+        classifier.predict(&["yearly", "support calls"]),
+        Some("stay")
+    );
+    assert_eq!(classifier.predict(&[]), Some("stay"));
+}
@@ -0,0 +1,41 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// Each test only uses some of the helpers.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::FrequentItemsetMiner;
+
+/// Returns a closure collecting item sets into `results`, with their items sorted, so the results
+/// of different algorithms can be compared.
+pub fn collect_into(results: &mut HashMap<Vec<String>, usize>) -> impl FnMut(&[&str], usize) + '_ {
+    |item_set, occurences| {
+        let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        item_set.sort();
+        assert!(results.insert(item_set, occurences).is_none());
+    }
+}
+
+/// The item sets found by `miner`, with their items sorted.
+pub fn mine(
+    miner: &dyn FrequentItemsetMiner,
+    transactions: &[Vec<&str>],
+    min_support: usize,
+) -> HashMap<Vec<String>, usize> {
+    let mut results = HashMap::new();
+    miner.mine(transactions, min_support, &mut collect_into(&mut results));
+    results
+}
@@ -0,0 +1,85 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use fpgrowth_rs::contrast_patterns;
+
+#[test]
+fn test_contrast_patterns() {
+    let mut north = vec![];
+    north.extend(vec![vec!["beer", "chips"]; 30]);
+    north.extend(vec![vec!["beer", "wine"]; 10]);
+    north.extend(vec![vec!["wine", "cheese"]; 10]);
+    let mut south = vec![];
+    south.extend(vec![vec!["beer", "wine"]; 10]);
+    south.extend(vec![vec!["wine", "cheese"]; 30]);
+    south.extend(vec![vec!["beer"]; 10]);
+
+    let mut patterns = vec![];
+    contrast_patterns(north.as_slice(), south.as_slice(), 0.3, 2.0, |pattern| {
+        patterns.push(pattern)
+    });
+
+    let item_sets = patterns
+        .iter()
+        .map(|p| p.item_set.join(","))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        item_sets,
+        vec![
+            "beer",
+            "beer,chips",
+            "cheese",
+            "cheese,wine",
+            "chips",
+            "wine"
+        ]
+    );
+
+    // Chips are only bought in the north.
+    let chips = &patterns[4];
+    assert!(chips.is_jumping());
+    assert_eq!(chips.occurences_a, 30);
+    assert_eq!(chips.occurences_b, 0);
+    assert_eq!(chips.growth_rate, f64::INFINITY);
+    assert_eq!(chips.support_difference, 0.6);
+
+    // Cheese is 3 times more frequent in the south.
+    let cheese = &patterns[2];
+    assert!(!cheese.is_jumping());
+    assert_eq!(cheese.support_a, 0.2);
+    assert_eq!(cheese.support_b, 0.6);
+    assert!((cheese.growth_rate - 1.0 / 3.0).abs() < 1e-9);
+    // 2x2 table of [[10, 40], [30, 20]].
+    assert!((cheese.chi_squared - 16.666666).abs() < 1e-5);
+    assert!(cheese.p_value < 1e-4);
+}
+
+#[test]
+fn test_contrast_patterns_p_value() {
+    // A 2x2 table of [[60, 40], [46, 54]] has a chi-squared statistic of about 3.93 and a p-value of
+    // about 0.047.
+    let mut a = vec![vec!["A"]; 60];
+    a.extend(vec![vec!["B"]; 40]);
+    let mut b = vec![vec!["A"]; 46];
+    b.extend(vec![vec!["B"]; 54]);
+
+    let mut patterns = vec![];
+    contrast_patterns(a.as_slice(), b.as_slice(), 0.5, 1.0, |pattern| {
+        patterns.push(pattern)
+    });
+
+    let pattern = patterns.iter().find(|p| p.item_set == vec!["A"]).unwrap();
+    assert!((pattern.chi_squared - 3.9343).abs() < 1e-3);
+    assert!((pattern.p_value - 0.0473).abs() < 1e-3);
+}
@@ -0,0 +1,142 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::DampedWindow;
+
+fn decayed_supports(
+    transactions: &[(u64, Vec<&str>)],
+    decay_rate: f64,
+    now: u64,
+    min_support: f64,
+) -> HashMap<Vec<String>, f64> {
+    let items = ["A", "B", "C", "D", "E"];
+    let mut supports = HashMap::new();
+    for mask in 1..(1 << items.len()) {
+        let item_set = items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, item)| item.to_string())
+            .collect::<Vec<_>>();
+        let support = transactions
+            .iter()
+            .filter(|(_, t)| item_set.iter().all(|i| t.contains(&i.as_str())))
+            .map(|(timestamp, _)| decay_rate.powf((now - timestamp) as f64))
+            .sum::<f64>();
+        if support >= min_support {
+            supports.insert(item_set, support);
+        }
+    }
+    supports
+}
+
+#[test]
+fn test_damped_window() {
+    let transactions = vec![
+        (0, vec!["E", "A", "D", "B"]),
+        (0, vec!["D", "A", "C", "E", "B"]),
+        (1, vec!["C", "A", "B", "E"]),
+        (2, vec!["B", "A", "D"]),
+        (2, vec!["D"]),
+        (4, vec!["D", "B"]),
+        (5, vec!["A", "D", "E"]),
+        (5, vec!["B", "C"]),
+    ];
+
+    let mut window = DampedWindow::new(0.9);
+    for (timestamp, transaction) in &transactions {
+        window.insert_transactions(*timestamp, std::slice::from_ref(transaction));
+    }
+
+    for now in [5, 10] {
+        let expected = decayed_supports(&transactions, 0.9, now, 2.0);
+        let mut results = HashMap::new();
+        window.mine(now, 2.0, |item_set, support| {
+            let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            item_set.sort();
+            results.insert(item_set, support);
+        });
+
+        assert!(!expected.is_empty());
+        assert_eq!(results.len(), expected.len());
+        for (item_set, support) in expected {
+            assert!(
+                (results[&item_set] - support).abs() < 1e-9,
+                "{:?}",
+                item_set
+            );
+        }
+    }
+
+    let num_transactions = transactions
+        .iter()
+        .map(|(timestamp, _)| 0.9_f64.powf((5 - timestamp) as f64))
+        .sum::<f64>();
+    assert!((window.num_transactions(5) - num_transactions).abs() < 1e-9);
+}
+
+#[test]
+fn test_damped_window_rescaling() {
+    let mut window = DampedWindow::new(0.5);
+    window.insert_transactions(0, &[vec!["A", "B"]]);
+    window.insert_transactions(1000, &[vec!["A", "B"], vec!["A"]]);
+    window.insert_transactions(1001, &[vec!["A", "C"]]);
+
+    let mut results = HashMap::new();
+    window.mine(1001, 0.5, |item_set, support| {
+        let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        item_set.sort();
+        results.insert(item_set, support);
+    });
+
+    assert_eq!(results.len(), 5);
+    assert!((results[&vec!["A".to_string()]] - 2.0).abs() < 1e-9);
+    assert!((results[&vec!["A".to_string(), "B".to_string()]] - 0.5).abs() < 1e-9);
+    assert!((results[&vec!["C".to_string()]] - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_damped_window_bounded() {
+    let items = [
+        "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P",
+    ];
+    let transactions = (0..5000_usize)
+        .map(|timestamp| {
+            // A different subset of the items at each timestamp.
+            let mask = timestamp * 40503 % (1 << items.len());
+            items
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, item)| *item)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // Weights are rescaled every 333 timestamps, and transactions older than 53 timestamps are
+    // negligible by then, so the tree never holds the paths of more than 386 transactions.
+    let mut window = DampedWindow::new(0.5);
+    for (timestamp, transaction) in transactions.iter().enumerate() {
+        window.insert_transactions(timestamp as u64, std::slice::from_ref(transaction));
+        assert!(window.num_nodes() <= 386 * items.len());
+    }
+
+    let mut results = HashMap::new();
+    window.mine(4999, 1.0, |item_set, support| {
+        results.insert(item_set.join(","), support);
+    });
+    assert!(!results.is_empty());
+}
@@ -0,0 +1,72 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use fpgrowth_rs::{Apriori, AutoMiner, DEclat, Eclat, FpGrowth, FrequentItemsetMiner};
+
+const MINERS: [&dyn FrequentItemsetMiner; 3] = [&Eclat, &DEclat, &AutoMiner];
+
+#[test]
+fn test_eclat_dense() {
+    // Survey-like data, where most answers are common, so dEclat switches to diffsets.
+    let transactions = vec![
+        vec!["A", "B", "C", "D", "E"],
+        vec!["A", "B", "C", "D", "E"],
+        vec!["A", "B", "C", "D"],
+        vec!["A", "B", "C", "E"],
+        vec!["A", "B", "D", "E"],
+        vec!["A", "C", "D", "E"],
+        vec!["B", "C", "D", "E"],
+        vec!["A", "B", "C", "D", "E"],
+    ];
+    for min_support in [3, 5, 6] {
+        let expected = common::mine(&FpGrowth, &transactions, min_support);
+        assert!(!expected.is_empty());
+        for miner in MINERS {
+            assert_eq!(common::mine(miner, &transactions, min_support), expected);
+        }
+    }
+}
+
+#[test]
+fn test_eclat_sparse() {
+    // Rare items have tidsets sparse enough to be stored as lists of ids.
+    let mut transactions = vec![vec!["A", "B"]; 60];
+    transactions.extend(vec![vec!["A", "C"]; 40]);
+    transactions.extend([
+        vec!["A", "X", "Y"],
+        vec!["B", "X", "Y"],
+        vec!["X"],
+        vec!["Y", "Z"],
+    ]);
+    for min_support in [1, 2, 3, 40] {
+        let expected = common::mine(&FpGrowth, &transactions, min_support);
+        assert!(!expected.is_empty());
+        for miner in MINERS {
+            assert_eq!(common::mine(miner, &transactions, min_support), expected);
+        }
+    }
+}
+
+#[test]
+fn test_zero_min_support() {
+    // Item sets that never occur are not frequent, as with a minimum support of one.
+    let transactions = vec![vec!["A", "B"], vec!["A"], vec!["C"]];
+    let expected = common::mine(&FpGrowth, &transactions, 1);
+    let miners: [&dyn FrequentItemsetMiner; 5] = [&FpGrowth, &Apriori, &Eclat, &DEclat, &AutoMiner];
+    for miner in miners {
+        assert_eq!(common::mine(miner, &transactions, 0), expected);
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::high_utility_item_sets;
+
+#[test]
+fn test_high_utility_item_sets() {
+    let transactions = vec![
+        vec![("A", 1), ("C", 1), ("D", 1)],
+        vec![("A", 2), ("C", 6), ("E", 2), ("G", 5)],
+        vec![("A", 1), ("B", 2), ("C", 1), ("D", 6), ("E", 1), ("F", 5)],
+        vec![("B", 4), ("C", 3), ("D", 3), ("E", 1)],
+        vec![("B", 2), ("C", 2), ("E", 1), ("G", 2)],
+    ];
+    let profits = HashMap::from([
+        ("A", 5),
+        ("B", 2),
+        ("C", 1),
+        ("D", 2),
+        ("E", 3),
+        ("F", 1),
+        ("G", 1),
+    ]);
+
+    let mut high_utility_item_sets_found = HashMap::new();
+    high_utility_item_sets(
+        transactions.as_slice(),
+        &profits,
+        30,
+        |item_set, utility| {
+            let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            item_set.sort();
+            high_utility_item_sets_found.insert(item_set, utility);
+        },
+    );
+
+    // Computes the expected results by checking every combination of items.
+    let items = ["A", "B", "C", "D", "E", "F", "G"];
+    let mut expected = HashMap::new();
+    for mask in 1..(1 << items.len()) {
+        let item_set = items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, item)| *item)
+            .collect::<Vec<_>>();
+        let utility = transactions
+            .iter()
+            .filter(|t| item_set.iter().all(|i| t.iter().any(|(item, _)| item == i)))
+            .flat_map(|t| t.iter().filter(|(item, _)| item_set.contains(item)))
+            .map(|(item, quantity)| quantity * profits[item])
+            .sum::<usize>();
+        if utility >= 30 {
+            let item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            expected.insert(item_set, utility);
+        }
+    }
+
+    assert!(!expected.is_empty());
+    assert_eq!(high_utility_item_sets_found, expected);
+    assert_eq!(
+        high_utility_item_sets_found.get(&vec![
+            "B".to_string(),
+            "C".to_string(),
+            "D".to_string(),
+            "E".to_string()
+        ]),
+        Some(&40)
+    );
+}
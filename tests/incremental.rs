@@ -0,0 +1,130 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::{fp_growth, CanTree};
+
+fn collect_into(results: &mut HashMap<Vec<String>, usize>) -> impl FnMut(&[&str], usize) + '_ {
+    |item_set, occurences| {
+        let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        item_set.sort();
+        assert!(results.insert(item_set, occurences).is_none());
+    }
+}
+
+#[test]
+fn test_can_tree() {
+    let transactions = vec![
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+    ];
+
+    let mut can_tree = CanTree::new();
+    can_tree.insert_transactions(&transactions[..5]);
+
+    let mut expected = HashMap::new();
+    fp_growth(&transactions[..5], 2, collect_into(&mut expected));
+    let mut results = HashMap::new();
+    can_tree.mine(2, collect_into(&mut results));
+    assert_eq!(results, expected);
+
+    can_tree.insert_transactions(&transactions[5..]);
+    assert_eq!(can_tree.num_transactions(), transactions.len());
+
+    for min_support in 1..=5 {
+        let mut expected = HashMap::new();
+        fp_growth(
+            transactions.as_slice(),
+            min_support,
+            collect_into(&mut expected),
+        );
+        let mut results = HashMap::new();
+        can_tree.mine(min_support, collect_into(&mut results));
+        assert_eq!(results, expected);
+    }
+}
+
+#[test]
+fn test_can_tree_merge() {
+    let transactions = vec![
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+    ];
+
+    let mut can_tree = CanTree::new();
+    for shard in transactions.chunks(3) {
+        let mut shard_tree = CanTree::new();
+        shard_tree.insert_transactions(shard);
+        can_tree.merge(shard_tree);
+    }
+    assert_eq!(can_tree.num_transactions(), transactions.len());
+
+    for min_support in 1..=5 {
+        let mut expected = HashMap::new();
+        fp_growth(
+            transactions.as_slice(),
+            min_support,
+            collect_into(&mut expected),
+        );
+        let mut results = HashMap::new();
+        can_tree.mine(min_support, collect_into(&mut results));
+        assert_eq!(results, expected);
+    }
+}
+
+#[test]
+#[should_panic(expected = "transaction was not")]
+fn test_can_tree_remove_missing() {
+    let mut can_tree = CanTree::new();
+    can_tree.insert_transactions(&[vec!["A", "B"]]);
+    can_tree.remove_transactions(&[vec!["A", "C"]]);
+}
+
+#[test]
+fn test_can_tree_remove_prefix() {
+    let mut can_tree = CanTree::new();
+    can_tree.insert_transactions(&[vec!["A", "B"], vec!["A", "B"]]);
+
+    // A transaction that is only a prefix of the inserted ones was never inserted.
+    let removed = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        can_tree.remove_transactions(&[vec!["A"]])
+    }));
+    assert!(removed.is_err());
+
+    let mut results = HashMap::new();
+    can_tree.mine(1, collect_into(&mut results));
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[&vec!["A".to_string(), "B".to_string()]], 2);
+
+    // Once inserted, the prefix can be removed without changing the longer transactions.
+    can_tree.insert_transactions(&[vec!["A"]]);
+    can_tree.remove_transactions(&[vec!["A"]]);
+    let mut after = HashMap::new();
+    can_tree.mine(1, collect_into(&mut after));
+    assert_eq!(after, results);
+    assert_eq!(can_tree.num_transactions(), 2);
+}
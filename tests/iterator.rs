@@ -0,0 +1,86 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "parallel")]
+mod common;
+
+use fpgrowth_rs::{fp_growth, fp_growth_iter};
+
+fn transactions() -> Vec<Vec<&'static str>> {
+    vec![
+        vec!["A", "B", "C", "D", "E", "F", "G", "H"],
+        vec!["A", "B", "C", "D", "E", "F"],
+        vec!["C", "D", "E", "F", "G", "H"],
+        vec!["A", "B", "E", "F", "G", "H"],
+        vec!["A", "C", "E", "G"],
+        vec!["B", "D", "F", "H"],
+        vec!["A", "B", "C", "D"],
+        vec!["E", "F", "G", "H"],
+        vec!["A", "D", "E", "H"],
+        vec!["B", "C", "F", "G"],
+    ]
+}
+
+fn to_strings(item_set: &[&str]) -> Vec<String> {
+    item_set.iter().map(|i| i.to_string()).collect()
+}
+
+#[test]
+fn test_iterator() {
+    let transactions = transactions();
+    let mut expected = vec![];
+    fp_growth(transactions.as_slice(), 2, |item_set, occurences| {
+        expected.push((to_strings(item_set), occurences))
+    });
+
+    let item_sets = fp_growth_iter(transactions.as_slice(), 2)
+        .map(|(item_set, occurences)| (to_strings(&item_set), occurences))
+        .collect::<Vec<_>>();
+    assert!(expected.len() > 50);
+    assert_eq!(item_sets, expected);
+
+    let first = fp_growth_iter(transactions.as_slice(), 2)
+        .take(10)
+        .map(|(item_set, occurences)| (to_strings(&item_set), occurences))
+        .collect::<Vec<_>>();
+    assert_eq!(first, expected[..10]);
+    assert_eq!(fp_growth_iter(&[], 1).next(), None);
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_iterator() {
+    use std::collections::HashMap;
+
+    use fpgrowth_rs::par_fp_growth_iter;
+    use rayon::prelude::*;
+
+    let transactions = transactions();
+    let mut expected = HashMap::new();
+    fp_growth(
+        transactions.as_slice(),
+        2,
+        common::collect_into(&mut expected),
+    );
+
+    let item_sets = par_fp_growth_iter(transactions.as_slice(), 2).collect::<Vec<_>>();
+    let mut results = HashMap::new();
+    {
+        let mut collect = common::collect_into(&mut results);
+        for (item_set, occurences) in item_sets {
+            collect(&item_set, occurences);
+        }
+    }
+    assert_eq!(results, expected);
+}
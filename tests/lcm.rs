@@ -0,0 +1,111 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::{fp_growth, lcm, ClosedItemsetMiner, Lcm};
+
+/// Transactions where every transaction holds Z, so the closure of the empty item set isn't empty.
+fn transactions() -> Vec<Vec<&'static str>> {
+    vec![
+        vec!["Z", "A", "B", "C", "D"],
+        vec!["Z", "A", "B", "C"],
+        vec!["Z", "A", "B", "D", "E"],
+        vec!["Z", "A", "C", "E"],
+        vec!["Z", "A", "B"],
+        vec!["Z", "B", "D"],
+        vec!["Z", "A", "E", "F"],
+        vec!["Z", "C", "F"],
+        vec!["Z", "A", "B", "C", "D", "E", "F"],
+        vec!["Z"],
+    ]
+}
+
+/// The closed item sets among the frequent item sets found by `fp_growth`.
+fn closed_item_sets(transactions: &[Vec<&str>], min_support: usize) -> HashMap<Vec<String>, usize> {
+    let mut item_sets = HashMap::new();
+    fp_growth(
+        transactions,
+        min_support,
+        common::collect_into(&mut item_sets),
+    );
+    item_sets
+        .iter()
+        .filter(|(item_set, occurences)| {
+            !item_sets.iter().any(|(superset, superset_occurences)| {
+                superset.len() > item_set.len()
+                    && superset_occurences == *occurences
+                    && item_set.iter().all(|i| superset.contains(i))
+            })
+        })
+        .map(|(item_set, occurences)| (item_set.clone(), *occurences))
+        .collect()
+}
+
+#[test]
+fn test_lcm() {
+    let transactions = vec![
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+    ];
+
+    let mut results = HashMap::new();
+    lcm(transactions.as_slice(), 3, |item_set, occurences| {
+        results.insert(item_set.join(","), occurences);
+    });
+
+    // C and E are not closed, as C is always found with B, and E with A.
+    let expected = [
+        ("A", 5),
+        ("B", 6),
+        ("D", 6),
+        ("A,B", 4),
+        ("A,D", 4),
+        ("B,C", 3),
+        ("B,D", 4),
+        ("A,E", 4),
+        ("A,B,D", 3),
+        ("A,B,E", 3),
+        ("A,D,E", 3),
+    ];
+    assert_eq!(
+        results,
+        expected
+            .into_iter()
+            .map(|(item_set, occurences)| (item_set.to_string(), occurences))
+            .collect::<HashMap<_, _>>()
+    );
+}
+
+#[test]
+fn test_lcm_matches_fp_growth() {
+    let transactions = transactions();
+    for min_support in [1, 2, 3, 5, 10] {
+        let mut results = HashMap::new();
+        Lcm.mine(
+            &transactions,
+            min_support,
+            &mut common::collect_into(&mut results),
+        );
+        assert_eq!(results, closed_item_sets(&transactions, min_support));
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod common;
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::{fp_growth, LossyCounter};
+
+/// Generates transactions where items earlier in the alphabet are more frequent.
+fn transactions(count: usize) -> Vec<Vec<&'static str>> {
+    let items = ["A", "B", "C", "D", "E", "F", "G", "H"];
+    let mut seed = 42_u64;
+    let mut transactions = vec![];
+    for _ in 0..count {
+        let mut transaction = vec![];
+        for (i, item) in items.iter().enumerate() {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            if (seed >> 33).is_multiple_of(i as u64 + 2) {
+                transaction.push(*item);
+            }
+        }
+        transactions.push(transaction);
+    }
+    transactions
+}
+
+#[test]
+fn test_lossy_counting() {
+    let transactions = transactions(3000);
+    let epsilon = 0.01;
+    let min_support = 0.05;
+
+    let mut lossy_counter = LossyCounter::new(epsilon, 500);
+    for chunk in transactions.chunks(70) {
+        lossy_counter.insert_transactions(chunk);
+    }
+    assert_eq!(lossy_counter.num_transactions(), transactions.len());
+
+    let mut results = HashMap::new();
+    lossy_counter.mine(min_support, common::collect_into(&mut results));
+
+    let mut exact = HashMap::new();
+    fp_growth(transactions.as_slice(), 1, common::collect_into(&mut exact));
+
+    let num_transactions = transactions.len() as f64;
+    let max_error = epsilon * num_transactions;
+    for (item_set, occurences) in &exact {
+        if *occurences as f64 >= min_support * num_transactions {
+            assert!(results.contains_key(item_set), "{:?}", item_set);
+        }
+    }
+    for (item_set, occurences) in &results {
+        let exact_occurences = exact[item_set];
+        assert!(*occurences <= exact_occurences);
+        assert!((exact_occurences - occurences) as f64 <= max_error);
+        assert!(*occurences as f64 >= (min_support - epsilon) * num_transactions);
+    }
+
+    // Only a fraction of the item sets is kept in memory.
+    assert!(lossy_counter.num_entries() < exact.len());
+}
+
+#[test]
+fn test_mine_partial_batch() {
+    let items = [
+        "A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K", "L", "M", "N", "O", "P",
+    ];
+    let mut lossy_counter = LossyCounter::new(0.01, 1000);
+    lossy_counter.insert_transactions(&[items.to_vec()]);
+
+    // Mining doesn't track the item sets of the transactions waiting for a full batch.
+    for _ in 0..5 {
+        let mut count = 0;
+        lossy_counter.mine(0.5, |_, occurences| {
+            assert_eq!(occurences, 1);
+            count += 1;
+        });
+        assert_eq!(count, (1 << items.len()) - 1);
+        assert_eq!(lossy_counter.num_entries(), 0);
+    }
+
+    // Buffered transactions are counted with the item sets already being tracked.
+    let mut lossy_counter = LossyCounter::new(0.1, 100);
+    let batch = [vec!["A", "B"], vec!["A"]];
+    for _ in 0..50 {
+        lossy_counter.insert_transactions(&batch);
+    }
+    let num_entries = lossy_counter.num_entries();
+    lossy_counter.insert_transactions(&[vec!["A", "B"], vec!["C"]]);
+    let mut results = HashMap::new();
+    lossy_counter.mine(0.3, |item_set, occurences| {
+        results.insert(item_set.join(","), occurences);
+    });
+    assert_eq!(lossy_counter.num_entries(), num_entries);
+    assert_eq!(results["A"], 101);
+    assert_eq!(results["A,B"], 51);
+    assert!(!results.contains_key("C"));
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "parallel")]
+mod common;
+
 #[cfg(feature = "parallel")]
 use fpgrowth_rs::parallel_fp_growth;
 
@@ -37,3 +40,180 @@ fn test_single_threaded() {
 
     assert_eq!(frequent_item_sets.len(), 15);
 }
+
+#[cfg(feature = "parallel")]
+fn transactions() -> Vec<Vec<&'static str>> {
+    vec![
+        vec!["A", "B", "C", "D", "E", "F", "G", "H"],
+        vec!["A", "B", "C", "D", "E", "F"],
+        vec!["C", "D", "E", "F", "G", "H"],
+        vec!["A", "B", "E", "F", "G", "H"],
+        vec!["A", "C", "E", "G"],
+        vec!["B", "D", "F", "H"],
+        vec!["A", "B", "C", "D"],
+        vec!["E", "F", "G", "H"],
+        vec!["A", "D", "E", "H"],
+        vec!["B", "C", "F", "G"],
+    ]
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_deterministic() {
+    use fpgrowth_rs::{fp_growth, ParallelFpGrowth};
+
+    let transactions = transactions();
+
+    let mut expected = vec![];
+    fp_growth(transactions.as_slice(), 3, |item_set, occurences| {
+        let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        item_set.sort();
+        expected.push((item_set, occurences));
+    });
+
+    for _ in 0..5 {
+        let mut frequent_item_sets = vec![];
+        ParallelFpGrowth::new().deterministic(true).mine(
+            transactions.as_slice(),
+            3,
+            |item_set, occurences| {
+                let item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+                frequent_item_sets.push((item_set, occurences));
+            },
+        );
+        assert_eq!(frequent_item_sets, expected);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_thread_pool_and_granularity() {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use fpgrowth_rs::{fp_growth, ParallelFpGrowth};
+
+    let transactions = transactions();
+    let mut expected = HashMap::new();
+    fp_growth(
+        transactions.as_slice(),
+        2,
+        common::collect_into(&mut expected),
+    );
+
+    let thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap(),
+    );
+    let miners = [
+        ParallelFpGrowth::new().num_threads(1),
+        ParallelFpGrowth::new().thread_pool(thread_pool.clone()),
+        ParallelFpGrowth::new().granularity(8),
+        ParallelFpGrowth::new().granularity(usize::MAX),
+        ParallelFpGrowth::new()
+            .thread_pool(thread_pool)
+            .granularity(8)
+            .deterministic(true),
+    ];
+    for miner in miners {
+        let mut results = HashMap::new();
+        miner.mine(
+            transactions.as_slice(),
+            2,
+            common::collect_into(&mut results),
+        );
+        assert_eq!(results, expected);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_batches_and_sink() {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use fpgrowth_rs::{fp_growth, ParallelFpGrowth};
+
+    let transactions = transactions();
+    let mut expected = HashMap::new();
+    fp_growth(
+        transactions.as_slice(),
+        2,
+        common::collect_into(&mut expected),
+    );
+
+    // A slow consumer, with a bounded channel of small batches.
+    let miners = [
+        ParallelFpGrowth::new().batch_size(3).channel_capacity(1),
+        ParallelFpGrowth::new()
+            .batch_size(1)
+            .channel_capacity(0)
+            .granularity(8),
+        ParallelFpGrowth::new()
+            .channel_capacity(1)
+            .deterministic(true),
+    ];
+    for miner in &miners {
+        let mut results = HashMap::new();
+        miner.mine(transactions.as_slice(), 2, |item_set, occurences| {
+            std::thread::yield_now();
+            let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            item_set.sort();
+            assert!(results.insert(item_set, occurences).is_none());
+        });
+        assert_eq!(results, expected);
+    }
+
+    for miner in &miners {
+        let results = Mutex::new(HashMap::new());
+        miner.mine_with_sink(transactions.as_slice(), 2, |batch| {
+            for (item_set, occurences) in batch.iter() {
+                let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+                item_set.sort();
+                assert!(results
+                    .lock()
+                    .unwrap()
+                    .insert(item_set, occurences)
+                    .is_none());
+            }
+        });
+        assert_eq!(results.into_inner().unwrap(), expected);
+    }
+
+    // Item sets are buffered by worker thread, so only the last batch of each thread is partial.
+    for batch_size in [1, 3, 256] {
+        let sizes = Mutex::new(vec![]);
+        ParallelFpGrowth::new()
+            .num_threads(2)
+            .batch_size(batch_size)
+            .mine_with_sink(transactions.as_slice(), 2, |batch| {
+                sizes.lock().unwrap().push(batch.len())
+            });
+        let sizes = sizes.into_inner().unwrap();
+        assert_eq!(sizes.iter().sum::<usize>(), expected.len());
+        assert!(sizes.iter().all(|size| *size > 0 && *size <= batch_size));
+        assert!(sizes.iter().filter(|size| **size < batch_size).count() <= 2);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_thread_pool_reused() {
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    use fpgrowth_rs::ParallelFpGrowth;
+
+    // Every call, and every clone, runs on the same two threads.
+    let transactions = transactions();
+    let threads = Mutex::new(HashSet::new());
+    let miner = ParallelFpGrowth::new().num_threads(2).batch_size(1);
+    for miner in [miner.clone(), miner.clone(), miner] {
+        miner.mine_with_sink(transactions.as_slice(), 2, |_| {
+            threads.lock().unwrap().insert(std::thread::current().id());
+        });
+    }
+    assert!(threads.into_inner().unwrap().len() <= 2);
+}
@@ -0,0 +1,109 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "parallel")]
+mod common;
+
+#[cfg(feature = "parallel")]
+use std::collections::HashMap;
+
+#[cfg(feature = "parallel")]
+use fpgrowth_rs::{fp_growth, pfp_growth, pfp_growth_top_k};
+
+#[cfg(feature = "parallel")]
+fn transactions() -> Vec<Vec<&'static str>> {
+    vec![
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+        vec!["F", "A", "C"],
+        vec!["F", "E", "D"],
+    ]
+}
+
+#[cfg(feature = "parallel")]
+fn frequent_item_sets(
+    transactions: &[Vec<&str>],
+    min_support: usize,
+) -> HashMap<Vec<String>, usize> {
+    let mut item_sets = HashMap::new();
+    fp_growth(
+        transactions,
+        min_support,
+        common::collect_into(&mut item_sets),
+    );
+    item_sets
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_pfp_growth() {
+    let transactions = transactions();
+    let expected = frequent_item_sets(&transactions, 2);
+    for num_groups in [1, 2, 3, 7, 100] {
+        let mut results = HashMap::new();
+        pfp_growth(
+            transactions.as_slice(),
+            2,
+            num_groups,
+            common::collect_into(&mut results),
+        );
+        assert_eq!(results, expected);
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_pfp_growth_top_k() {
+    let transactions = transactions();
+    let item_sets = frequent_item_sets(&transactions, 2);
+    let mut items = item_sets
+        .iter()
+        .filter(|(item_set, _)| item_set.len() == 1)
+        .map(|(item_set, occurences)| (item_set[0].clone(), *occurences))
+        .collect::<Vec<_>>();
+    items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let mut expected = vec![];
+    for (item, _) in &items {
+        let mut top = item_sets
+            .iter()
+            .filter(|(item_set, _)| item_set.contains(item))
+            .collect::<Vec<_>>();
+        top.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (item_set, occurences) in top.into_iter().take(5) {
+            expected.push((item.clone(), item_set.clone(), *occurences));
+        }
+    }
+
+    for num_groups in [1, 4] {
+        let mut results = vec![];
+        pfp_growth_top_k(
+            transactions.as_slice(),
+            2,
+            num_groups,
+            5,
+            |item, item_set, occurences| {
+                let item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+                results.push((item.to_string(), item_set, occurences));
+            },
+        );
+        assert_eq!(results, expected);
+    }
+}
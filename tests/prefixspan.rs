@@ -0,0 +1,172 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::{
+    generate_sequence_rules, prefix_span, prefix_span_with_constraints, SequenceConstraints,
+};
+
+/// The example database from the PrefixSpan paper.
+fn sequences() -> Vec<Vec<Vec<&'static str>>> {
+    vec![
+        vec![
+            vec!["a"],
+            vec!["a", "b", "c"],
+            vec!["a", "c"],
+            vec!["d"],
+            vec!["c", "f"],
+        ],
+        vec![vec!["a", "d"], vec!["c"], vec!["b", "c"], vec!["a", "e"]],
+        vec![
+            vec!["e", "f"],
+            vec!["a", "b"],
+            vec!["d", "f"],
+            vec!["c"],
+            vec!["b"],
+        ],
+        vec![
+            vec!["e"],
+            vec!["g"],
+            vec!["a", "f"],
+            vec!["c"],
+            vec!["b"],
+            vec!["c"],
+        ],
+    ]
+}
+
+fn to_owned(pattern: &[Vec<&str>]) -> Vec<Vec<String>> {
+    pattern
+        .iter()
+        .map(|element| element.iter().map(|i| i.to_string()).collect())
+        .collect()
+}
+
+/// Checks if the pattern can be found in the sequence after `start`, respecting the gaps.
+fn supports(
+    sequence: &[Vec<&str>],
+    pattern: &[Vec<String>],
+    start: usize,
+    constraints: &SequenceConstraints,
+    first: bool,
+) -> bool {
+    let Some((element, rest)) = pattern.split_first() else {
+        return true;
+    };
+    (start..sequence.len())
+        .filter(|position| {
+            first
+                || constraints
+                    .max_gap
+                    .is_none_or(|max_gap| *position - start <= max_gap)
+        })
+        .filter(|position| first || *position - start >= constraints.min_gap)
+        .filter(|position| {
+            element
+                .iter()
+                .all(|i| sequence[*position].contains(&i.as_str()))
+        })
+        .any(|position| supports(sequence, rest, position + 1, constraints, false))
+}
+
+#[test]
+fn test_prefix_span() {
+    let sequences = sequences();
+
+    let mut patterns = HashMap::new();
+    prefix_span(sequences.as_slice(), 2, |pattern, occurences| {
+        patterns.insert(to_owned(pattern), occurences);
+    });
+
+    assert_eq!(patterns.len(), 53);
+    let pattern = vec![
+        vec!["a".to_string(), "b".to_string()],
+        vec!["c".to_string()],
+    ];
+    assert_eq!(patterns.get(&pattern), Some(&2));
+    for (pattern, occurences) in &patterns {
+        let expected = sequences
+            .iter()
+            .filter(|s| supports(s, pattern, 0, &Default::default(), true))
+            .count();
+        assert_eq!(*occurences, expected, "{:?}", pattern);
+    }
+}
+
+#[test]
+fn test_prefix_span_with_constraints() {
+    let sequences = sequences();
+
+    let mut unconstrained = HashMap::new();
+    prefix_span(sequences.as_slice(), 2, |pattern, occurences| {
+        unconstrained.insert(to_owned(pattern), occurences);
+    });
+
+    let constraints = SequenceConstraints {
+        min_gap: 0,
+        max_gap: Some(1),
+        max_length: Some(3),
+    };
+    let mut patterns = HashMap::new();
+    prefix_span_with_constraints(
+        sequences.as_slice(),
+        2,
+        constraints,
+        |pattern, occurences| {
+            patterns.insert(to_owned(pattern), occurences);
+        },
+    );
+
+    // Constraints can only lower the support of a pattern, so the expected patterns are a subset of
+    // the unconstrained ones.
+    let expected = unconstrained
+        .keys()
+        .filter(|pattern| pattern.iter().map(|e| e.len()).sum::<usize>() <= 3)
+        .map(|pattern| {
+            let occurences = sequences
+                .iter()
+                .filter(|s| supports(s, pattern, 0, &constraints, true))
+                .count();
+            (pattern.clone(), occurences)
+        })
+        .filter(|(_, occurences)| *occurences >= 2)
+        .collect::<HashMap<_, _>>();
+
+    assert!(expected.len() < unconstrained.len());
+    assert_eq!(patterns, expected);
+}
+
+#[test]
+fn test_sequence_rules() {
+    let sequences = sequences();
+
+    let mut patterns = HashMap::new();
+    prefix_span(sequences.as_slice(), 2, |pattern, occurences| {
+        patterns.insert(to_owned(pattern), occurences);
+    });
+
+    let mut rules = vec![];
+    generate_sequence_rules(&patterns, sequences.len(), &mut |rule| rules.push(rule));
+
+    let rule = rules
+        .iter()
+        .find(|r| {
+            r.antecedent == vec![vec!["a".to_string(), "b".to_string()]]
+                && r.consequent == vec!["c".to_string()]
+        })
+        .unwrap();
+    assert_eq!(rule.confidence, 1.0);
+    assert_eq!(rule.lift, 1.0);
+}
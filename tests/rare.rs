@@ -0,0 +1,126 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::{
+    fp_growth, generate_rare_association_rules, minimal_rare_item_sets, rare_item_sets,
+};
+
+fn transactions() -> Vec<Vec<&'static str>> {
+    vec![
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+        vec!["F", "A"],
+    ]
+}
+
+fn all_item_sets(transactions: &[Vec<&str>]) -> HashMap<Vec<String>, usize> {
+    let mut item_sets = HashMap::new();
+    fp_growth(transactions, 1, |item_set, occurences| {
+        let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        item_set.sort();
+        item_sets.insert(item_set, occurences);
+    });
+    item_sets
+}
+
+#[test]
+fn test_rare_item_sets() {
+    let transactions = transactions();
+    let expected = all_item_sets(&transactions)
+        .into_iter()
+        .filter(|(_, occurences)| *occurences >= 2 && *occurences < 4)
+        .collect::<HashMap<_, _>>();
+
+    let mut results = HashMap::new();
+    rare_item_sets(transactions.as_slice(), 2, 4, |item_set, occurences| {
+        let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        item_set.sort();
+        results.insert(item_set, occurences);
+    });
+
+    assert!(!expected.is_empty());
+    assert_eq!(results, expected);
+}
+
+#[test]
+fn test_minimal_rare_item_sets() {
+    let transactions = transactions();
+    let item_sets = all_item_sets(&transactions);
+    let support = |item_set: &Vec<String>| item_sets.get(item_set).copied().unwrap_or(0);
+    let expected = item_sets
+        .iter()
+        .filter(|(item_set, occurences)| {
+            **occurences < 4
+                && (0..item_set.len()).all(|skip| {
+                    let mut subset = (*item_set).clone();
+                    subset.remove(skip);
+                    subset.is_empty() || support(&subset) >= 4
+                })
+        })
+        .map(|(item_set, occurences)| (item_set.clone(), *occurences))
+        .collect::<HashMap<_, _>>();
+
+    let mut results = HashMap::new();
+    minimal_rare_item_sets(transactions.as_slice(), 1, 4, |item_set, occurences| {
+        let item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        assert!(results.insert(item_set, occurences).is_none());
+    });
+
+    assert_eq!(results, expected);
+    assert_eq!(results.get(&vec!["F".to_string()]), Some(&1));
+    assert_eq!(
+        results.get(&vec!["B".to_string(), "E".to_string()]),
+        Some(&3)
+    );
+}
+
+#[test]
+fn test_rare_association_rules() {
+    let transactions = transactions();
+    let item_sets = all_item_sets(&transactions)
+        .into_iter()
+        .filter(|(_, occurences)| *occurences >= 2)
+        .collect::<HashMap<_, _>>();
+
+    let mut association_rules = vec![];
+    generate_rare_association_rules(&item_sets, transactions.len(), 3, &mut |association_rule| {
+        association_rules.push(association_rule)
+    });
+
+    // Each rare item set generates one rule for each split of its items in two non-empty sets.
+    let expected_rules = item_sets
+        .iter()
+        .filter(|(item_set, occurences)| item_set.len() > 1 && **occurences < 3)
+        .map(|(item_set, _)| (1 << item_set.len()) - 2)
+        .sum::<usize>();
+    assert!(expected_rules > 0);
+    assert_eq!(association_rules.len(), expected_rules);
+
+    let association_rule = association_rules
+        .iter()
+        .find(|r| {
+            r.antecedent_set == vec!["C".to_string()] && r.consequent_set == vec!["E".to_string()]
+        })
+        .unwrap();
+    assert_eq!(association_rule.combined_support, 2.0 / 9.0);
+    assert!((association_rule.confidence - 2.0 / 3.0).abs() < 1e-6);
+}
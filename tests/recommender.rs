@@ -0,0 +1,119 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use fpgrowth_rs::{Aggregation, AssociationRule, Recommender};
+
+fn rule(antecedent: &[&str], consequent: &[&str], lift: f32, confidence: f32) -> AssociationRule {
+    AssociationRule::new(
+        antecedent.iter().map(|i| i.to_string()).collect(),
+        consequent.iter().map(|i| i.to_string()).collect(),
+        0.5,
+        0.5,
+        0.25,
+        lift,
+        confidence,
+    )
+}
+
+fn recommender() -> Recommender {
+    let mut negative = rule(&["beer"], &["milk"], 2.0, 0.9);
+    negative.consequent_negated = true;
+    Recommender::new(vec![
+        rule(&["beer"], &["potato chips"], 1.5, 0.6),
+        rule(&["wine", "beer"], &["potato chips"], 1.2, 0.5),
+        rule(&["wine"], &["cheese"], 3.0, 0.7),
+        rule(&["cheese", "wine"], &["bread"], 1.1, 0.8),
+        rule(&["beer"], &["wine"], 1.3, 0.4),
+        rule(&["eggs"], &["flower"], 2.0, 0.9),
+        negative,
+    ])
+}
+
+fn items(recommender: &Recommender, basket: &[&str], aggregation: Aggregation) -> Vec<String> {
+    recommender
+        .recommend(basket, 10, aggregation)
+        .into_iter()
+        .map(|r| r.item)
+        .collect()
+}
+
+#[test]
+fn test_recommend() {
+    let recommender = recommender();
+    assert_eq!(recommender.rules().len(), 6);
+
+    let recommendations = recommender.recommend(&["wine", "beer"], 10, Aggregation::MaxConfidence);
+    let scores = recommendations
+        .iter()
+        .map(|r| (r.item.as_str(), r.score))
+        .collect::<Vec<_>>();
+    assert_eq!(scores, vec![("cheese", 0.7), ("potato chips", 0.6)]);
+    let rules = &recommendations[1].rules;
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].antecedent_set, vec!["beer".to_string()]);
+
+    assert_eq!(
+        items(&recommender, &["beer", "wine"], Aggregation::SumLift),
+        vec!["cheese", "potato chips"]
+    );
+    assert_eq!(
+        items(
+            &recommender,
+            &["cheese", "beer", "wine"],
+            Aggregation::SumConfidence
+        ),
+        vec!["potato chips", "bread"]
+    );
+    assert_eq!(
+        items(&recommender, &["beer"], Aggregation::MaxLift),
+        vec!["potato chips", "wine"]
+    );
+    assert!(items(&recommender, &["milk"], Aggregation::MaxLift).is_empty());
+    assert_eq!(
+        recommender
+            .recommend(&["beer"], 1, Aggregation::MaxLift)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_recommend_matches_linear_scan() {
+    let recommender = recommender();
+    let baskets = [
+        vec!["beer"],
+        vec!["wine", "cheese"],
+        vec!["eggs", "wine", "beer", "cheese"],
+        vec![],
+    ];
+    for basket in baskets {
+        let mut expected = recommender
+            .rules()
+            .iter()
+            .filter(|r| {
+                r.antecedent_set
+                    .iter()
+                    .all(|i| basket.contains(&i.as_str()))
+            })
+            .flat_map(|r| r.consequent_set.iter().cloned())
+            .filter(|i| !basket.contains(&i.as_str()))
+            .collect::<Vec<_>>();
+        expected.sort();
+        expected.dedup();
+
+        let mut results = items(&recommender, &basket, Aggregation::SumLift);
+        results.sort();
+        assert_eq!(results, expected);
+    }
+}
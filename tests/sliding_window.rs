@@ -0,0 +1,67 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::{fp_growth, SlidingWindow};
+
+fn collect_into(results: &mut HashMap<Vec<String>, usize>) -> impl FnMut(&[&str], usize) + '_ {
+    |item_set, occurences| {
+        let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        item_set.sort();
+        results.insert(item_set, occurences);
+    }
+}
+
+#[test]
+fn test_sliding_window() {
+    let transactions = [
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+    ];
+
+    let mut window = SlidingWindow::new(4);
+    for (timestamp, pane) in transactions.chunks(3).enumerate() {
+        window.insert_transactions(timestamp as u64, pane);
+    }
+    assert_eq!(window.len(), 4);
+
+    let mut expected = HashMap::new();
+    fp_growth(&transactions[4..], 2, collect_into(&mut expected));
+    let mut results = HashMap::new();
+    window.mine(2, collect_into(&mut results));
+    assert_eq!(results, expected);
+
+    // The transactions left from the second pane are expired.
+    window.expire_before(2);
+    assert_eq!(window.len(), 2);
+
+    let mut expected = HashMap::new();
+    fp_growth(&transactions[6..], 1, collect_into(&mut expected));
+    let mut results = HashMap::new();
+    window.mine(1, collect_into(&mut results));
+    assert_eq!(results, expected);
+
+    window.expire_before(3);
+    assert!(window.is_empty());
+    let mut results = HashMap::new();
+    window.mine(1, collect_into(&mut results));
+    assert!(results.is_empty());
+}
@@ -0,0 +1,88 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "async")]
+use fpgrowth_rs::{fp_growth, fp_growth_stream};
+#[cfg(feature = "async")]
+use futures::{executor::block_on, StreamExt};
+
+#[cfg(feature = "async")]
+fn transactions() -> Vec<Vec<&'static str>> {
+    vec![
+        vec!["A", "B", "C", "D", "E", "F", "G", "H"],
+        vec!["A", "B", "C", "D", "E", "F"],
+        vec!["C", "D", "E", "F", "G", "H"],
+        vec!["A", "B", "E", "F", "G", "H"],
+        vec!["A", "C", "E", "G"],
+        vec!["B", "D", "F", "H"],
+        vec!["A", "B", "C", "D"],
+        vec!["E", "F", "G", "H"],
+        vec!["A", "D", "E", "H"],
+        vec!["B", "C", "F", "G"],
+    ]
+}
+
+#[cfg(feature = "async")]
+fn to_strings(transactions: &[Vec<&str>]) -> Vec<Vec<String>> {
+    transactions
+        .iter()
+        .map(|t| t.iter().map(|i| i.to_string()).collect())
+        .collect()
+}
+
+/// The item sets found by `fp_growth`, in the order they are found.
+#[cfg(feature = "async")]
+fn item_sets(transactions: &[Vec<&str>], min_support: usize) -> Vec<(Vec<String>, usize)> {
+    let mut item_sets = vec![];
+    fp_growth(transactions, min_support, |item_set, occurences| {
+        item_sets.push((
+            item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+            occurences,
+        ))
+    });
+    item_sets
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_stream() {
+    let transactions = transactions();
+    let expected = item_sets(&transactions, 2);
+
+    let stream = fp_growth_stream(to_strings(&transactions), 2, 4);
+    let item_sets = block_on(stream.collect::<Vec<_>>());
+    assert!(expected.len() > 20);
+    assert_eq!(item_sets, expected);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn test_stream_cancelled() {
+    let transactions = transactions();
+    let expected = item_sets(&transactions, 2);
+
+    // With a buffer of one, mining waits for the stream, and stops once it is cancelled.
+    let mut stream = fp_growth_stream(to_strings(&transactions), 2, 1);
+    let first = block_on(async {
+        let mut first = vec![];
+        for _ in 0..3 {
+            first.push(stream.next().await.unwrap());
+        }
+        first
+    });
+    assert_eq!(first, expected[..3]);
+    let sent = stream.cancel();
+    assert!((3..=4).contains(&sent), "{}", sent);
+    assert!(sent < expected.len());
+}
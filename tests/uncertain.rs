@@ -0,0 +1,111 @@
+// Copyright 2023 Andre Cipriani Bandarra
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use fpgrowth_rs::{fp_growth, uf_growth};
+
+#[test]
+fn test_uf_growth() {
+    let transactions = vec![
+        vec![("A", 0.9), ("B", 0.5), ("D", 0.3)],
+        vec![("A", 0.9), ("B", 0.5), ("C", 0.7)],
+        vec![("A", 0.8), ("C", 0.7)],
+        vec![("A", 0.9), ("B", 0.6), ("C", 0.2), ("D", 1.0)],
+        vec![("B", 0.5), ("C", 0.7), ("D", 0.4)],
+        vec![("A", 0.1), ("D", 0.9)],
+    ];
+
+    let mut results = HashMap::new();
+    uf_growth(
+        transactions.as_slice(),
+        1.0,
+        |item_set, expected_support| {
+            let mut item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            item_set.sort();
+            results.insert(item_set, expected_support);
+        },
+    );
+
+    // Computes the expected supports by checking every combination of items.
+    let items = ["A", "B", "C", "D"];
+    let mut expected = HashMap::new();
+    for mask in 1..(1 << items.len()) {
+        let item_set = items
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask & (1 << i) != 0)
+            .map(|(_, item)| *item)
+            .collect::<Vec<_>>();
+        let expected_support = transactions
+            .iter()
+            .map(|t| {
+                item_set
+                    .iter()
+                    .map(|i| {
+                        t.iter()
+                            .find(|(item, _)| item == i)
+                            .map_or(0.0, |(_, p)| *p)
+                    })
+                    .product::<f64>()
+            })
+            .sum::<f64>();
+        if expected_support >= 1.0 {
+            let item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            expected.insert(item_set, expected_support);
+        }
+    }
+
+    assert_eq!(results.len(), expected.len());
+    for (item_set, expected_support) in expected {
+        let support = results.get(&item_set).unwrap();
+        assert!((support - expected_support).abs() < 1e-9, "{:?}", item_set);
+    }
+}
+
+#[test]
+fn test_uf_growth_with_certain_items() {
+    let transactions = vec![
+        vec!["E", "A", "D", "B"],
+        vec!["D", "A", "C", "E", "B"],
+        vec!["C", "A", "B", "E"],
+        vec!["B", "A", "D"],
+        vec!["D"],
+        vec!["D", "B"],
+        vec!["A", "D", "E"],
+        vec!["B", "C"],
+    ];
+    let uncertain_transactions = transactions
+        .iter()
+        .map(|t| t.iter().map(|i| (*i, 1.0)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let mut expected = vec![];
+    fp_growth(transactions.as_slice(), 3, |item_set, occurences| {
+        let item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+        expected.push((item_set, occurences as f64));
+    });
+
+    let mut results = vec![];
+    uf_growth(
+        uncertain_transactions.as_slice(),
+        3.0,
+        |item_set, expected_support| {
+            let item_set = item_set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+            results.push((item_set, expected_support));
+        },
+    );
+
+    assert_eq!(results, expected);
+}